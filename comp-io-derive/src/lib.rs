@@ -0,0 +1,228 @@
+//! # Comp IO Derive
+//!
+//! Derive macros for `comp_io`. Enable the `derive` feature of `comp_io` to use them through
+//! `comp_io::Readable`
+
+use proc_macro::{Delimiter, Group, Literal, TokenStream, TokenTree};
+
+/// Derives `comp_io::Readable` for a struct, reading its fields in declaration order
+///
+/// Works for structs with named fields, tuple structs and unit structs
+///
+/// # Example:
+///
+/// ```ignore
+/// #[derive(comp_io::Readable)]
+/// struct Edge {
+///     u: usize,
+///     v: usize,
+///     w: i64,
+/// }
+///
+/// let edge: Edge = reader.next_t().unwrap();
+/// ```
+#[proc_macro_derive(Readable)]
+pub fn derive_readable(input: TokenStream) -> TokenStream {
+    match readable_impl(input) {
+        Ok(tokens) => tokens,
+        Err(message) => compile_error(&message),
+    }
+}
+
+fn readable_impl(input: TokenStream) -> Result<TokenStream, String> {
+    let mut tokens = skip_attributes_and_visibility(input.into_iter().collect());
+    match tokens.next() {
+        Some(TokenTree::Ident(keyword)) if keyword.to_string() == "struct" => {}
+        _ => return Err("`#[derive(Readable)]` only supports structs".to_string()),
+    }
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name.to_string(),
+        _ => return Err("expected a struct name".to_string()),
+    };
+    let body = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+            let fields = split_fields(group)
+                .into_iter()
+                .map(|field| field_name(field).map(|field| format!("{field}: ::comp_io::Readable::read(reader)?,")))
+                .collect::<Result<String, _>>()?;
+            format!("{name} {{ {fields} }}")
+        }
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            let fields = "::comp_io::Readable::read(reader)?,".repeat(split_fields(group).len());
+            format!("{name}({fields})")
+        }
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ';' => name.clone(),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+            return Err("`#[derive(Readable)]` doesn't support generic structs".to_string())
+        }
+        _ => return Err("expected struct fields".to_string()),
+    };
+
+    let output = format!(
+        "impl ::comp_io::Readable for {name} {{
+            fn read<R: ::std::io::Read>(reader: &mut ::comp_io::Reader<R>) -> ::core::option::Option<Self> {{
+                ::core::option::Option::Some({body})
+            }}
+        }}"
+    );
+    output.parse().map_err(|_| "failed to generate the Readable impl".to_string())
+}
+
+/// Derives `comp_io::ReadableEnum` and `comp_io::Readable` for an enum of unit variants
+///
+/// Each variant is read from a token matching its name, ignoring case. Use `#[token = "..."]`
+/// on a variant to match an exact token instead
+///
+/// # Example:
+///
+/// ```ignore
+/// #[derive(comp_io::ReadableEnum)]
+/// enum Query {
+///     Add,
+///     Remove,
+///     #[token = "?"]
+///     Ask,
+/// }
+///
+/// match reader.next_t::<Query>().unwrap() {
+///     Query::Add => {}
+///     Query::Remove => {}
+///     Query::Ask => {}
+/// }
+/// ```
+#[proc_macro_derive(ReadableEnum, attributes(token))]
+pub fn derive_readable_enum(input: TokenStream) -> TokenStream {
+    match readable_enum_impl(input) {
+        Ok(tokens) => tokens,
+        Err(message) => compile_error(&message),
+    }
+}
+
+fn readable_enum_impl(input: TokenStream) -> Result<TokenStream, String> {
+    let mut tokens = skip_attributes_and_visibility(input.into_iter().collect());
+    match tokens.next() {
+        Some(TokenTree::Ident(keyword)) if keyword.to_string() == "enum" => {}
+        _ => return Err("`#[derive(ReadableEnum)]` only supports enums".to_string()),
+    }
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name.to_string(),
+        _ => return Err("expected an enum name".to_string()),
+    };
+    let variants = match tokens.next() {
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => split_fields(group),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+            return Err("`#[derive(ReadableEnum)]` doesn't support generic enums".to_string())
+        }
+        _ => return Err("expected enum variants".to_string()),
+    };
+
+    let mut arms = String::new();
+    for variant in variants {
+        let (variant, token) = parse_variant(variant)?;
+        let condition = match token {
+            Some(token) => format!("token == {token}.as_bytes()"),
+            None => format!("token.eq_ignore_ascii_case({:?}.as_bytes())", variant),
+        };
+        arms += &format!("if {condition} {{ return ::core::option::Option::Some({name}::{variant}); }}");
+    }
+
+    let output = format!(
+        "impl ::comp_io::ReadableEnum for {name} {{
+            fn from_token(token: &[u8]) -> ::core::option::Option<Self> {{
+                {arms}
+                ::core::option::Option::None
+            }}
+        }}
+
+        impl ::comp_io::Readable for {name} {{
+            fn read<R: ::std::io::Read>(reader: &mut ::comp_io::Reader<R>) -> ::core::option::Option<Self> {{
+                reader.next_enum()
+            }}
+        }}"
+    );
+    output.parse().map_err(|_| "failed to generate the ReadableEnum impl".to_string())
+}
+
+/// Returns the name of a unit variant and the literal from its `#[token = "..."]` attribute, if any
+fn parse_variant(variant: Vec<TokenTree>) -> Result<(String, Option<Literal>), String> {
+    let mut token = None;
+    let mut tokens = variant.into_iter().peekable();
+    while let Some(TokenTree::Punct(punct)) = tokens.peek() {
+        if punct.as_char() != '#' {
+            break;
+        }
+        tokens.next();
+        if let Some(TokenTree::Group(attribute)) = tokens.next() {
+            let attribute: Vec<_> = attribute.stream().into_iter().collect();
+            if let [TokenTree::Ident(key), TokenTree::Punct(eq), TokenTree::Literal(value)] = &attribute[..] {
+                if key.to_string() == "token" && eq.as_char() == '=' {
+                    token = Some(value.clone());
+                }
+            }
+        }
+    }
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name.to_string(),
+        _ => return Err("expected a variant name".to_string()),
+    };
+    match tokens.next() {
+        None => Ok((name, token)),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => Ok((name, token)), // explicit discriminant
+        _ => Err(format!("`#[derive(ReadableEnum)]` only supports unit variants, `{name}` has fields")),
+    }
+}
+
+/// Skips leading `#[...]` attributes and a visibility such as `pub` or `pub(crate)`
+fn skip_attributes_and_visibility(tokens: Vec<TokenTree>) -> std::iter::Peekable<std::vec::IntoIter<TokenTree>> {
+    let mut tokens = tokens.into_iter().peekable();
+    loop {
+        match tokens.peek() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {
+                tokens.next();
+                tokens.next(); // the [...] group
+            }
+            Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                tokens.next();
+                if let Some(TokenTree::Group(group)) = tokens.peek() {
+                    if group.delimiter() == Delimiter::Parenthesis {
+                        tokens.next();
+                    }
+                }
+            }
+            _ => return tokens,
+        }
+    }
+}
+
+/// Splits the contents of a field list on top-level commas, ignoring commas inside generic arguments
+fn split_fields(group: Group) -> Vec<Vec<TokenTree>> {
+    let (mut fields, mut current, mut depth) = (Vec::new(), Vec::new(), 0);
+    for token in group.stream() {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == '<' => depth += 1,
+            TokenTree::Punct(punct) if punct.as_char() == '>' => depth -= 1,
+            TokenTree::Punct(punct) if punct.as_char() == ',' && depth == 0 => {
+                fields.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(token);
+    }
+    if !current.is_empty() {
+        fields.push(current);
+    }
+    fields
+}
+
+/// Returns the name of a named field, given its tokens
+fn field_name(field: Vec<TokenTree>) -> Result<String, String> {
+    match skip_attributes_and_visibility(field).next() {
+        Some(TokenTree::Ident(name)) => Ok(name.to_string()),
+        _ => Err("expected a field name".to_string()),
+    }
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("::core::compile_error!({message:?});").parse().unwrap()
+}
@@ -0,0 +1,90 @@
+use std::io::Read;
+
+use num_bigint::{BigInt, BigUint};
+
+use crate::{ReadErrorKind, Readable, Reader};
+
+impl<R: Read> Reader<R> {
+    /// Reads the next integer of any length from stdin, with an optional sign
+    ///
+    /// The digits are parsed straight out of the buffer, without building a `String` first
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("-123456789012345678901234567890");
+    /// let n = reader.next_bigint().unwrap();
+    /// assert_eq!(n.to_string(), "-123456789012345678901234567890");
+    /// ```
+    pub fn next_bigint(&mut self) -> Option<BigInt> {
+        let token = self.next_token_bytes()?;
+        let value = decimal(token).and_then(|digits| BigInt::parse_bytes(digits, 10));
+        if value.is_none() {
+            self.failure = Some(ReadErrorKind::Invalid);
+        }
+        value
+    }
+
+    /// Reads the next non-negative integer of any length from stdin, same as `next_bigint`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("99999999999999999999999 -1");
+    /// assert_eq!(reader.next_biguint().unwrap().to_string(), "99999999999999999999999");
+    /// assert_eq!(reader.next_biguint(), None);
+    /// ```
+    pub fn next_biguint(&mut self) -> Option<BigUint> {
+        let token = self.next_token_bytes()?;
+        let value = decimal(token).and_then(|digits| BigUint::parse_bytes(digits, 10));
+        if value.is_none() {
+            self.failure = Some(ReadErrorKind::Invalid);
+        }
+        value
+    }
+}
+
+/// Returns `token` if it is an optional sign followed by decimal digits. `parse_bytes` also allows `_` separators,
+/// which other number readers don't
+fn decimal(token: &[u8]) -> Option<&[u8]> {
+    let digits = token.strip_prefix(b"-").or_else(|| token.strip_prefix(b"+")).unwrap_or(token);
+    (!digits.is_empty() && digits.iter().all(u8::is_ascii_digit)).then_some(token)
+}
+
+impl Readable for BigInt {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        reader.next_bigint()
+    }
+}
+
+impl Readable for BigUint {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        reader.next_biguint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_bigint() {
+        let digits = "9".repeat(1000);
+        let input = format!("{digits} -{digits} +7 0 1_000 12a -");
+        let mut reader = Reader::from_str(&input);
+        assert_eq!(reader.next_bigint().unwrap().to_string(), digits);
+        assert_eq!(reader.next_bigint().unwrap().to_string(), format!("-{digits}"));
+        assert_eq!(reader.next_bigint(), Some(BigInt::from(7)));
+        assert_eq!(reader.next_t::<BigInt>(), Some(BigInt::from(0)));
+        assert_eq!(reader.next_bigint(), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Invalid));
+        assert_eq!(reader.next_bigint(), None);
+        assert_eq!(reader.next_bigint(), None);
+        assert_eq!(reader.next_bigint(), None);
+
+        let mut reader = Reader::from_str("18446744073709551616 -5");
+        assert_eq!(reader.next_t::<BigUint>(), Some(BigUint::from(u64::MAX) + 1u32));
+        assert_eq!(reader.next_biguint(), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Invalid));
+    }
+}
@@ -0,0 +1,65 @@
+use std::fmt;
+use std::ops::Deref;
+
+#[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+use crate::mmap::Mmap;
+
+/// The bytes a reader parses from, either owned or mapped straight from a file
+pub(crate) enum Buffer {
+    Owned(Vec<u8>),
+    #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+    Mapped(Mmap),
+}
+
+impl Buffer {
+    /// Returns the owned bytes, copying a mapping out first so more input can be appended to it
+    pub(crate) fn vec_mut(&mut self) -> &mut Vec<u8> {
+        #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+        if let Buffer::Mapped(map) = self {
+            *self = Buffer::Owned(map.to_vec());
+        }
+        match self {
+            Buffer::Owned(vec) => vec,
+            #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+            Buffer::Mapped(_) => unreachable!(),
+        }
+    }
+}
+
+impl Deref for Buffer {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        match self {
+            Buffer::Owned(vec) => vec,
+            #[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+            Buffer::Mapped(map) => map,
+        }
+    }
+}
+
+/// A mapping is cloned into owned bytes, so the copy doesn't depend on the file
+impl Clone for Buffer {
+    fn clone(&self) -> Self {
+        Buffer::Owned(self.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for Buffer {
+    fn from(vec: Vec<u8>) -> Self {
+        Buffer::Owned(vec)
+    }
+}
+
+impl PartialEq<Vec<u8>> for Buffer {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        **self == **other
+    }
+}
+
+impl fmt::Debug for Buffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
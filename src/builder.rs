@@ -0,0 +1,132 @@
+use std::io::{self, Read};
+
+use crate::Reader;
+
+/// How much whitespace number readers accept before a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Whitespace {
+    /// Values are separated by exactly one delimiter, which is the fastest
+    #[default]
+    Single,
+    /// Any amount of whitespace may come before a value
+    Lenient,
+}
+
+/// Collects the options for a `Reader` before choosing its source, created by `Reader::builder`
+///
+/// # Example:
+///
+/// ```
+/// let mut reader = comp_io::Reader::builder()
+///     .decimal_separator(b',')
+///     .crlf(false)
+///     .source(&b"2,5\n"[..]);
+/// assert_eq!(reader.next_f64(), Some(2.5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReaderBuilder {
+    capacity: usize,
+    whitespace: Whitespace,
+    crlf: bool,
+    decimal_separator: u8,
+    interactive: bool,
+    resync: bool,
+}
+
+impl Default for ReaderBuilder {
+    fn default() -> Self {
+        ReaderBuilder {
+            capacity: 400_000,
+            whitespace: Whitespace::Single,
+            crlf: true,
+            decimal_separator: b'.',
+            interactive: false,
+            resync: false,
+        }
+    }
+}
+
+impl ReaderBuilder {
+    /// Sets how many bytes each refill reads, 400KB by default
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `capacity` is 0
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be positive");
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets how much whitespace number readers accept before a value, `Whitespace::Single` by default
+    pub fn whitespace(mut self, whitespace: Whitespace) -> Self {
+        self.whitespace = whitespace;
+        self
+    }
+
+    /// Sets whether `\r\n` after a value is consumed as a single delimiter, on by default.
+    /// Turning it off saves a check when the input is known to use `\n`
+    pub fn crlf(mut self, crlf: bool) -> Self {
+        self.crlf = crlf;
+        self
+    }
+
+    /// Same as `Reader::with_decimal_separator`
+    pub fn decimal_separator(mut self, separator: u8) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Same as `Reader::interactive`
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Same as `Reader::with_resync`
+    pub fn resync(mut self, resync: bool) -> Self {
+        self.resync = resync;
+        self
+    }
+
+    /// Builds a reader over `source`
+    pub fn source<R: Read>(self, source: R) -> Reader<R> {
+        let mut reader = Reader::with_source(source, Vec::with_capacity(self.capacity));
+        reader.capacity = self.capacity;
+        reader.whitespace = self.whitespace;
+        reader.crlf = self.crlf;
+        reader.decimal_separator = self.decimal_separator;
+        reader.interactive = self.interactive;
+        reader.resync = self.resync;
+        reader
+    }
+
+    /// Builds a reader over stdin, locked for the reader's lifetime like `Reader::new`
+    pub fn stdin(self) -> Reader {
+        self.source(io::stdin().lock())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let input = io::Cursor::new("\t7\r\n\r\n  8.5 0x1f\n");
+        let mut reader = Reader::builder().capacity(2).whitespace(Whitespace::Lenient).source(input);
+        assert_eq!(reader.next_i32(), Some(7));
+        assert_eq!(reader.next_f64(), Some(8.5));
+        assert_eq!(reader.next_hex_u64(), Some(31));
+        assert_eq!(reader.next_i32(), None);
+
+        let mut reader = Reader::builder().whitespace(Whitespace::Lenient).source(&b"  yes   1"[..]);
+        assert_eq!(reader.next_bool(), Some(true));
+        assert_eq!(reader.next_i32(), Some(1));
+
+        let mut reader = Reader::builder().crlf(false).source(&b"1\r\n2"[..]);
+        assert_eq!(reader.next_i32(), Some(1));
+        assert_eq!(reader.next_char(), Some('\n'));
+        assert_eq!(reader.next_i32(), Some(2));
+    }
+}
@@ -0,0 +1,108 @@
+use std::cell::RefCell;
+use std::io::{Read, Write};
+
+use crate::{Reader, Writer};
+
+thread_local! {
+    static IO: RefCell<Option<Io>> = const { RefCell::new(None) };
+}
+
+/// A reader and writer pair behind the `read!` and `put!` macros, stored per thread so short
+/// solutions don't have to pass them through every function
+///
+/// Nothing is set up until the macros are first used, at which point `Io::new` is installed unless
+/// another `Io` already was
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::{put, read};
+///
+/// # let input: Box<dyn std::io::Read> = Box::new(std::io::Cursor::new("3\n1 2 3\n"));
+/// # comp_io::Io { reader: comp_io::Reader::from_reader(input), writer: comp_io::Writer::auto() }.install();
+/// let n = read!(usize);
+/// let a = read!([i64; n]);
+/// put!(n, a.iter().sum::<i64>());
+/// ```
+pub struct Io {
+    pub reader: Reader<Box<dyn Read>>,
+    pub writer: Writer<Box<dyn Write>>,
+}
+
+impl Io {
+    /// Instantiates the default pair of `Reader::auto` and `Writer::auto`
+    pub fn new() -> Self {
+        Io {
+            reader: Reader::auto(),
+            writer: Writer::auto(),
+        }
+    }
+
+    /// Makes this the `Io` used by the macros on the current thread, dropping and flushing any previous one
+    pub fn install(self) {
+        IO.with(|io| *io.borrow_mut() = Some(self));
+    }
+
+    /// Removes the current thread's `Io`, so its output can be flushed or its input inspected
+    pub fn take() -> Option<Io> {
+        IO.with(|io| io.borrow_mut().take())
+    }
+
+    /// Runs `f` on the current thread's `Io`, installing `Io::new` first if there isn't one
+    ///
+    /// The output is flushed when the thread exits, or earlier with `Io::with(|io| io.writer.flush())`
+    ///
+    /// # Panics:
+    ///
+    /// Panics if called from inside `f`
+    pub fn with<T>(f: impl FnOnce(&mut Io) -> T) -> T {
+        IO.with(|io| f(io.borrow_mut().get_or_insert_with(Io::new)))
+    }
+}
+
+impl Default for Io {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor};
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::{put, read};
+
+    struct Shared(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_global_io() {
+        let out = Rc::new(RefCell::new(Vec::new()));
+        let input: Box<dyn Read> = Box::new(Cursor::new("2 x\n5 -1\n7"));
+        Io {
+            reader: Reader::from_reader(input),
+            writer: Writer::from_writer(Box::new(Shared(out.clone()))),
+        }
+        .install();
+
+        let (n, c) = read!(usize, char);
+        let a = read!([i64; n]);
+        put!(c, a[0] + a[1], "ok");
+        put!();
+        put!(read!(i64));
+        drop(Io::take());
+        assert_eq!(out.borrow().as_slice(), b"x 4 ok\n\n7\n");
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::VecDeque;
+use std::io::Read;
+use std::ops::Index;
+
+use crate::{Readable, Reader};
+
+/// Whether vertices in the input are numbered from 0 or from 1
+///
+/// Graphs are always returned with vertices numbered from 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indexing {
+    ZeroIndexed,
+    OneIndexed,
+}
+
+/// Whether an edge `u v` can also be followed from `v` to `u`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Directed,
+    Undirected,
+}
+
+/// A graph in compressed sparse row form, with every adjacency list stored back to back in one array
+///
+/// Created by `Reader::read_csr`, and indexed by vertex like the `Vec<Vec<usize>>` from `Reader::read_graph`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Csr {
+    /// The neighbors of `v` are `targets[offsets[v]..offsets[v + 1]]`
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+}
+
+impl Csr {
+    /// Returns the neighbors of `v`
+    pub fn neighbors(&self, v: usize) -> &[usize] {
+        &self.targets[self.offsets[v]..self.offsets[v + 1]]
+    }
+
+    /// Returns the number of vertices
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns `true` if the graph has no vertices
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Index<usize> for Csr {
+    type Output = [usize];
+
+    fn index(&self, v: usize) -> &[usize] {
+        self.neighbors(v)
+    }
+}
+
+/// Returns the vertices of a tree in breadth-first order from `root`, so every vertex comes after its parent
+///
+/// `tree` can be either the children lists from `Reader::read_tree_parents` or the adjacency lists from
+/// `Reader::read_tree_edges`. Iterating the order backwards visits children before their parents.
+/// The order is empty if `root` is not a vertex of the tree
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::Indexing::OneIndexed;
+///
+/// let mut reader = comp_io::Reader::from_str("3 1 1\n");
+/// let children = reader.read_tree_parents(4, OneIndexed).unwrap();
+/// assert_eq!(comp_io::bfs_order(&children, 0), vec![0, 2, 3, 1]);
+/// ```
+pub fn bfs_order(tree: &[Vec<usize>], root: usize) -> Vec<usize> {
+    if root >= tree.len() {
+        return Vec::new();
+    }
+    let mut seen = vec![false; tree.len()];
+    let mut order = Vec::with_capacity(tree.len());
+    let mut queue = VecDeque::from([root]);
+    seen[root] = true;
+    while let Some(v) = queue.pop_front() {
+        order.push(v);
+        for &u in &tree[v] {
+            if !seen[u] {
+                seen[u] = true;
+                queue.push_back(u);
+            }
+        }
+    }
+    order
+}
+
+impl<R: Read> Reader<R> {
+    /// Reads a vertex of a graph with `n` vertices, returning it numbered from 0
+    ///
+    /// Returns `None` if the vertex is not in the graph
+    fn next_vertex(&mut self, n: usize, indexing: Indexing) -> Option<usize> {
+        let v = self.next_usize()?;
+        let v = match indexing {
+            Indexing::ZeroIndexed => v,
+            Indexing::OneIndexed => v.checked_sub(1)?,
+        };
+        (v < n).then_some(v)
+    }
+
+    /// Reads `m` edges `u v` of a graph with `n` vertices into adjacency lists
+    ///
+    /// Returns `None` if an edge can't be read or names a vertex outside the graph
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use comp_io::{Direction::Undirected, Indexing::OneIndexed};
+    ///
+    /// let mut reader = comp_io::Reader::from_str("1 2\n2 3\n");
+    /// let graph = reader.read_graph(3, 2, OneIndexed, Undirected).unwrap();
+    /// assert_eq!(graph, vec![vec![1], vec![0, 2], vec![1]]);
+    /// ```
+    pub fn read_graph(
+        &mut self,
+        n: usize,
+        m: usize,
+        indexing: Indexing,
+        direction: Direction,
+    ) -> Option<Vec<Vec<usize>>> {
+        let mut graph = vec![Vec::new(); n];
+        for _ in 0..m {
+            let u = self.next_vertex(n, indexing)?;
+            let v = self.next_vertex(n, indexing)?;
+            graph[u].push(v);
+            if direction == Direction::Undirected {
+                graph[v].push(u);
+            }
+        }
+        Some(graph)
+    }
+
+    /// Reads `m` edges `u v w` of a graph with `n` vertices into adjacency lists of `(vertex, weight)`
+    ///
+    /// Returns `None` if an edge can't be read or names a vertex outside the graph
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use comp_io::{Direction::Directed, Indexing::OneIndexed};
+    ///
+    /// let mut reader = comp_io::Reader::from_str("1 2 5\n1 3 -1\n");
+    /// let graph = reader.read_weighted_graph::<i64>(3, 2, OneIndexed, Directed).unwrap();
+    /// assert_eq!(graph, vec![vec![(1, 5), (2, -1)], vec![], vec![]]);
+    /// ```
+    pub fn read_weighted_graph<W: Readable + Clone>(
+        &mut self,
+        n: usize,
+        m: usize,
+        indexing: Indexing,
+        direction: Direction,
+    ) -> Option<Vec<Vec<(usize, W)>>> {
+        let mut graph = vec![Vec::new(); n];
+        for _ in 0..m {
+            let u = self.next_vertex(n, indexing)?;
+            let v = self.next_vertex(n, indexing)?;
+            let w = W::read(self)?;
+            if direction == Direction::Undirected {
+                graph[v].push((u, w.clone()));
+            }
+            graph[u].push((v, w));
+        }
+        Some(graph)
+    }
+
+    /// Reads `m` edges `u v` of a graph with `n` vertices into a `Csr`, with the neighbors of each
+    /// vertex in the same order as `read_graph`
+    ///
+    /// Every array is allocated once at its final size, which is much faster than `read_graph` for
+    /// millions of edges
+    ///
+    /// Returns `None` if an edge can't be read or names a vertex outside the graph
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use comp_io::{Direction::Undirected, Indexing::OneIndexed};
+    ///
+    /// let mut reader = comp_io::Reader::from_str("1 2\n2 3\n");
+    /// let graph = reader.read_csr(3, 2, OneIndexed, Undirected).unwrap();
+    /// assert_eq!(graph[1], [0, 2]);
+    /// ```
+    pub fn read_csr(&mut self, n: usize, m: usize, indexing: Indexing, direction: Direction) -> Option<Csr> {
+        let mut edges = Vec::with_capacity(m);
+        let mut offsets = vec![0; n + 1];
+        for _ in 0..m {
+            let u = self.next_vertex(n, indexing)?;
+            let v = self.next_vertex(n, indexing)?;
+            edges.push((u, v));
+            offsets[u] += 1;
+            if direction == Direction::Undirected {
+                offsets[v] += 1;
+            }
+        }
+        // offsets[v] becomes the end of v's neighbors, and is moved back to the start while they are
+        // placed, in reverse so each list keeps the input order
+        let mut end = 0;
+        for offset in &mut offsets {
+            end += *offset;
+            *offset = end;
+        }
+        let mut targets = vec![0; end];
+        for &(u, v) in edges.iter().rev() {
+            if direction == Direction::Undirected {
+                offsets[v] -= 1;
+                targets[offsets[v]] = u;
+            }
+            offsets[u] -= 1;
+            targets[offsets[u]] = v;
+        }
+        Some(Csr { offsets, targets })
+    }
+
+    /// Reads the parents of the vertices of a rooted tree with `n` vertices, other than the root, into
+    /// lists of children
+    ///
+    /// The root is the first vertex, so the parents of vertices `2..=n` are read if `indexing` is
+    /// `OneIndexed`, and of vertices `1..n` otherwise
+    ///
+    /// Returns `None` if a parent can't be read or is outside the tree
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use comp_io::Indexing::OneIndexed;
+    ///
+    /// let mut reader = comp_io::Reader::from_str("1 1 2\n");
+    /// let children = reader.read_tree_parents(4, OneIndexed).unwrap();
+    /// assert_eq!(children, vec![vec![1, 2], vec![3], vec![], vec![]]);
+    /// ```
+    pub fn read_tree_parents(&mut self, n: usize, indexing: Indexing) -> Option<Vec<Vec<usize>>> {
+        let mut children = vec![Vec::new(); n];
+        for v in 1..n {
+            let parent = self.next_vertex(n, indexing)?;
+            children[parent].push(v);
+        }
+        Some(children)
+    }
+
+    /// Reads the `n - 1` edges of a tree with `n` vertices into adjacency lists
+    ///
+    /// Returns `None` if an edge can't be read or names a vertex outside the tree
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use comp_io::Indexing::OneIndexed;
+    ///
+    /// let mut reader = comp_io::Reader::from_str("3\n1 2\n3 2\n");
+    /// let n = reader.next_usize().unwrap();
+    /// let tree = reader.read_tree_edges(n, OneIndexed).unwrap();
+    /// assert_eq!(tree, vec![vec![1], vec![0, 2], vec![1]]);
+    /// ```
+    pub fn read_tree_edges(&mut self, n: usize, indexing: Indexing) -> Option<Vec<Vec<usize>>> {
+        self.read_graph(n, n.saturating_sub(1), indexing, Direction::Undirected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_graph() {
+        let mut reader = Reader::from_str("0 1\n2 1\n1 1\n");
+        let graph = reader.read_graph(3, 3, Indexing::ZeroIndexed, Direction::Directed).unwrap();
+        assert_eq!(graph, vec![vec![1], vec![1], vec![1]]);
+
+        let mut reader = Reader::from_str("1 2\n2 4\n");
+        assert_eq!(reader.read_graph(3, 2, Indexing::OneIndexed, Direction::Undirected), None);
+        let mut reader = Reader::from_str("0 1\n");
+        assert_eq!(reader.read_graph(3, 1, Indexing::OneIndexed, Direction::Undirected), None);
+    }
+
+    #[test]
+    fn test_read_csr() {
+        let input = "2 1\n0 2\n2 2\n3 0\n1 2\n";
+        for direction in [Direction::Directed, Direction::Undirected] {
+            let graph = Reader::from_str(input).read_graph(4, 5, Indexing::ZeroIndexed, direction).unwrap();
+            let csr = Reader::from_str(input).read_csr(4, 5, Indexing::ZeroIndexed, direction).unwrap();
+            assert_eq!(csr.len(), 4);
+            for (v, neighbors) in graph.iter().enumerate() {
+                assert_eq!(&csr[v], neighbors.as_slice());
+            }
+        }
+        assert!(Reader::from_str("").read_csr(0, 0, Indexing::OneIndexed, Direction::Directed).unwrap().is_empty());
+        assert_eq!(Reader::from_str("1 3").read_csr(2, 1, Indexing::OneIndexed, Direction::Directed), None);
+    }
+
+    #[test]
+    fn test_read_tree() {
+        let mut reader = Reader::from_str("0 0 1 1\n1 2\n1 3\n3 0\n");
+        let children = reader.read_tree_parents(5, Indexing::ZeroIndexed).unwrap();
+        assert_eq!(children, vec![vec![1, 2], vec![3, 4], vec![], vec![], vec![]]);
+        assert_eq!(bfs_order(&children, 0), vec![0, 1, 2, 3, 4]);
+
+        let tree = reader.read_tree_edges(4, Indexing::ZeroIndexed).unwrap();
+        assert_eq!(tree, vec![vec![3], vec![2, 3], vec![1], vec![1, 0]]);
+        assert_eq!(bfs_order(&tree, 2), vec![2, 1, 3, 0]);
+        assert_eq!(bfs_order(&[], 0), vec![]);
+        assert_eq!(bfs_order(&tree, 4), vec![]);
+
+        assert_eq!(Reader::from_str("").read_tree_edges(1, Indexing::OneIndexed), Some(vec![vec![]]));
+        assert_eq!(Reader::from_str("1 4").read_tree_parents(3, Indexing::OneIndexed), None);
+    }
+
+    #[test]
+    fn test_read_weighted_graph() {
+        let mut reader = Reader::from_str("0 1 2.5\n1 2 -1\n");
+        let graph = reader.read_weighted_graph::<f64>(3, 2, Indexing::ZeroIndexed, Direction::Undirected).unwrap();
+        assert_eq!(graph, vec![vec![(1, 2.5)], vec![(0, 2.5), (2, -1.0)], vec![(1, -1.0)]]);
+
+        let mut reader = Reader::from_str("1 2 x\n");
+        assert_eq!(reader.read_weighted_graph::<u32>(2, 1, Indexing::OneIndexed, Direction::Directed), None);
+    }
+}
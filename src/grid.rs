@@ -0,0 +1,109 @@
+use std::io::Read;
+
+use crate::Reader;
+
+impl<R: Read> Reader<R> {
+    /// Reads `rows` whitespace-delimited rows of a board, such as a maze of `#` and `.`, into a grid of bytes
+    ///
+    /// Returns `None` if a row can't be read or the rows don't all have the same length
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("2 3\n#.#\n..#\n");
+    /// let rows = reader.next_usize().unwrap();
+    /// let _cols = reader.next_usize().unwrap();
+    /// let grid = reader.read_grid(rows).unwrap();
+    /// assert_eq!(grid[1][2], b'#');
+    /// ```
+    pub fn read_grid(&mut self, rows: usize) -> Option<Vec<Vec<u8>>> {
+        let grid = (0..rows).map(|_| self.next_bytes()).collect::<Option<Vec<_>>>()?;
+        equal_lengths(&grid).then_some(grid)
+    }
+
+    /// Same as `read_grid`, reading each row as `char`s so boards can use non-ASCII symbols
+    ///
+    /// Returns `None` if a row is not valid UTF-8, and rows are compared by their number of `char`s
+    pub fn read_grid_chars(&mut self, rows: usize) -> Option<Vec<Vec<char>>> {
+        let grid = (0..rows).map(|_| self.next_chars()).collect::<Option<Vec<_>>>()?;
+        equal_lengths(&grid).then_some(grid)
+    }
+
+    /// Reads `rows` rows of `cols` bytes into a `(rows + 2) x (cols + 2)` grid with a border of `sentinel`,
+    /// so neighbors can be looked at from any cell inside without bounds checks
+    ///
+    /// The cell in row `r` and column `c` of the input is at `grid[r + 1][c + 1]`.
+    /// Returns `None` if a row can't be read or isn't `cols` long
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str(".#\n..\n");
+    /// let grid = reader.read_grid_padded(2, 2, b'#').unwrap();
+    /// assert_eq!(grid, [b"####", b"#.##", b"#..#", b"####"]);
+    /// ```
+    pub fn read_grid_padded(&mut self, rows: usize, cols: usize, sentinel: u8) -> Option<Vec<Vec<u8>>> {
+        let mut grid = vec![vec![sentinel; cols + 2]; rows + 2];
+        for padded in &mut grid[1..=rows] {
+            let row = self.next_token_bytes().filter(|row| row.len() == cols)?;
+            padded[1..=cols].copy_from_slice(row);
+        }
+        Some(grid)
+    }
+
+    /// Reads `rows` rows of `cols` digits written without separators, such as `0110`, into a grid of their values
+    ///
+    /// Returns `None` if a row can't be read, isn't `cols` long, or contains anything but digits
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("305\n117\n");
+    /// let grid = reader.read_digit_grid(2, 3).unwrap();
+    /// assert_eq!(grid, vec![vec![3, 0, 5], vec![1, 1, 7]]);
+    /// ```
+    pub fn read_digit_grid(&mut self, rows: usize, cols: usize) -> Option<Vec<Vec<u8>>> {
+        (0..rows)
+            .map(|_| {
+                let row = self.next_token_bytes().filter(|row| row.len() == cols)?;
+                row.iter().map(|&c| c.is_ascii_digit().then(|| c - b'0')).collect()
+            })
+            .collect()
+    }
+}
+
+fn equal_lengths<T>(grid: &[Vec<T>]) -> bool {
+    grid.windows(2).all(|rows| rows[0].len() == rows[1].len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_grid() {
+        let mut reader = Reader::from_str("#.\r\n.#\r\nab\nabc\n");
+        assert_eq!(reader.read_grid(2), Some(vec![b"#.".to_vec(), b".#".to_vec()]));
+        assert_eq!(reader.read_grid(2), None);
+        assert_eq!(Reader::from_str("x").read_grid(2), None);
+
+        let mut reader = Reader::from_str("╔╗\n╚╝\n");
+        assert_eq!(reader.read_grid_chars(2), Some(vec![vec!['╔', '╗'], vec!['╚', '╝']]));
+    }
+
+    #[test]
+    fn test_read_grid_padded() {
+        let mut reader = Reader::from_str("abc\n");
+        assert_eq!(reader.read_grid_padded(1, 3, 0), Some(vec![vec![0; 5], b"\0abc\0".to_vec(), vec![0; 5]]));
+        assert_eq!(Reader::from_str("").read_grid_padded(0, 0, b'#'), Some(vec![b"##".to_vec(); 2]));
+        assert_eq!(Reader::from_str("ab\nabc").read_grid_padded(2, 2, b'#'), None);
+    }
+
+    #[test]
+    fn test_read_digit_grid() {
+        let mut reader = Reader::from_str("09\n90\n1a\n123\n");
+        assert_eq!(reader.read_digit_grid(2, 2), Some(vec![vec![0, 9], vec![9, 0]]));
+        assert_eq!(reader.read_digit_grid(1, 2), None);
+        assert_eq!(reader.read_digit_grid(1, 2), None);
+    }
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::io::Read;
+use std::rc::Rc;
+
+use crate::Reader;
+
+/// Maps tokens to dense `u32` ids, so repeated names can be compared and indexed as integers
+///
+/// # Example:
+///
+/// ```
+/// let mut reader = comp_io::Reader::from_str("paris rome paris");
+/// let mut cities = comp_io::Interner::new();
+///
+/// assert_eq!(reader.next_interned(&mut cities), Some(0));
+/// assert_eq!(reader.next_interned(&mut cities), Some(1));
+/// assert_eq!(reader.next_interned(&mut cities), Some(0));
+/// assert_eq!(cities.resolve(1), b"rome");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    // both share each token's single allocation
+    ids: HashMap<Rc<[u8]>, u32>,
+    tokens: Vec<Rc<[u8]>>,
+}
+
+impl Interner {
+    /// Instantiates an empty interner
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of `token`, assigning the next free id if it hasn't been seen before
+    ///
+    /// Only allocates when the token is new, and then only once
+    pub fn intern(&mut self, token: &[u8]) -> u32 {
+        if let Some(&id) = self.ids.get(token) {
+            return id;
+        }
+        let id = self.tokens.len() as u32;
+        let token: Rc<[u8]> = Rc::from(token);
+        self.ids.insert(Rc::clone(&token), id);
+        self.tokens.push(token);
+        id
+    }
+
+    /// Returns the id of `token` if it has been interned
+    pub fn get(&self, token: &[u8]) -> Option<u32> {
+        self.ids.get(token).copied()
+    }
+
+    /// Returns the token with the given id
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `id` wasn't returned by this interner
+    pub fn resolve(&self, id: u32) -> &[u8] {
+        &self.tokens[id as usize]
+    }
+
+    /// Returns the number of distinct tokens interned so far
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Returns `true` if no tokens have been interned
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+}
+
+impl<R: Read> Reader<R> {
+    /// Reads the next whitespace-delimited token from stdin and returns its id in `interner`
+    pub fn next_interned(&mut self, interner: &mut Interner) -> Option<u32> {
+        Some(interner.intern(self.next_token_bytes()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_interned() {
+        let mut reader = Reader::from_str("b a b c a");
+        let mut interner = Interner::new();
+        let ids: Vec<_> = (0..5).map(|_| reader.next_interned(&mut interner).unwrap()).collect();
+        assert_eq!(ids, vec![0, 1, 0, 2, 1]);
+        assert_eq!(interner.len(), 3);
+        assert_eq!(interner.get(b"c"), Some(2));
+        assert_eq!(interner.get(b"d"), None);
+        assert_eq!(interner.resolve(0), b"b");
+        // the map and the id table share one copy
+        assert_eq!(Rc::strong_count(&interner.tokens[0]), 2);
+        assert_eq!(reader.next_interned(&mut interner), None);
+    }
+}
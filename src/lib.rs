@@ -1,15 +1,61 @@
 //! # Comp IO
 //!
-//! `comp_io` is a collection of utilities centered around the `Reader` struct
+//! `comp_io` is a collection of utilities centered around the `Reader` and `Writer` structs
 //! to make competitive programming easier to write
 
-use std::io::{self, Read};
+#[cfg(feature = "bigint")]
+mod bigint;
+mod buffer;
+mod builder;
+mod global;
+mod graph;
+mod grid;
+mod interner;
+mod macros;
+#[cfg(all(feature = "mmap", unix, target_pointer_width = "64"))]
+mod mmap;
+mod prefetch;
+mod readable;
+mod tee;
+mod validator;
+mod writer;
+
+pub use builder::{ReaderBuilder, Whitespace};
+pub use global::Io;
+pub use graph::{bfs_order, Csr, Direction, Indexing};
+pub use interner::Interner;
+pub use prefetch::Prefetch;
+pub use readable::{Bytes, Chars, Iter, Readable, ReadableEnum, Usize1};
+pub use tee::Tee;
+pub use validator::Validator;
+pub use writer::{Integer, Scope, Writable, Writer};
+
+/// Derives `Readable` for structs, reading the fields in declaration order. Requires the `derive` feature
+#[cfg(feature = "derive")]
+pub use comp_io_derive::Readable;
+
+/// Derives `ReadableEnum` and `Readable` for enums of unit variants. Requires the `derive` feature
+#[cfg(feature = "derive")]
+pub use comp_io_derive::ReadableEnum;
+
+use buffer::Buffer;
+
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, Read};
+use std::ops::{Range, RangeBounds};
+use std::path::Path;
+use std::process::{Child, ChildStdout};
+use std::str::FromStr;
 
 /// Reads data from stdin in an optimized manner
 ///
+/// The source defaults to stdin, but can be any type implementing `Read`, such as a file or a socket
+///
 /// Limitations: doesn't skip whitespace. Assumes that input data is sanitized (each number is separated by exactly 1 character)
 /// This allows for faster reading of data, because in most competitive programming scenarios, the input data is already provided
-/// in such a way
+/// in such a way. Windows `\r\n` line endings are treated the same as `\n`
 ///
 /// # Example:
 ///
@@ -25,195 +71,2282 @@ use std::io::{self, Read};
 /// // Read an f64
 /// let d: f64 = reader.next_f64().unwrap();
 /// ```
-pub struct Reader {
-    buffer: Vec<u8>,
+///
+/// Readers over cloneable sources, such as in-memory input, can be cloned to parse the rest of the input more than once
+#[derive(Clone)]
+pub struct Reader<R = io::StdinLock<'static>> {
+    source: R,
+    buffer: Buffer,
     index: usize,
+    /// Number of bytes at the start of `buffer` that hold input, the rest is room for the next refill
     len: usize,
+    /// Holds the bytes of the current float so it can be handed to `str::parse` when the fast path can't be used
+    scratch: Vec<u8>,
+    decimal_separator: u8,
+    /// Set once the source returns 0 bytes from a read, or from the start for in-memory input, meaning there is nothing left to read
+    exhausted: bool,
+    /// Refills stop after a single read, so a reply can be parsed before the next line has been sent
+    interactive: bool,
+    /// Number of bytes each refill tries to read
+    capacity: usize,
+    whitespace: Whitespace,
+    /// Whether `\r\n` after a value is consumed as one delimiter
+    crlf: bool,
+    /// After a failed parse, skip the rest of the bad token so the next read starts at a fresh one
+    resync: bool,
+    /// Why the last number read failed, if it failed on malformed input
+    failure: Option<ReadErrorKind>,
+    /// Number of newlines in input that was already dropped from the buffer
+    discarded_lines: usize,
+    /// Number of bytes of the current line that were already dropped from the buffer
+    discarded_column: usize,
+    /// Number of bytes that were already dropped from the buffer, so offsets into the input stay valid across refills
+    discarded_bytes: usize,
+    /// Bytes handed back by `unread` that differ from the input, kept apart so the input itself is never changed.
+    /// The last one is read first
+    pushback: Vec<u8>,
+    /// `len`, or 0 while there are pushed back bytes, so `next` only checks a single bound before taking from the buffer
+    fast_len: usize,
 }
 //        ___      _________________
 //       /  .\    /                 \
 //      /  =__|  <  Walrus Approved.|
 // hjm /    ||    \_________________/
-impl Iterator for Reader {
+impl<R: Read> Iterator for Reader<R> {
     type Item = u8;
 
-    #[cfg(not(test))]
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        // If at end of buffer
-        if self.index >= self.len {
-            if self.len < 400_000 {
+        if self.index >= self.fast_len {
+            if !self.pushback.is_empty() {
+                return self.pop_pushback();
+            }
+            if self.index >= self.len && !self.refill(self.len) {
                 return None;
             }
-            // Try to read from stdin
-            self.buffer.clear(); // necessary?
-            self.len = io::stdin()
-                .lock()
-                .take(400_000)
-                .read_to_end(&mut self.buffer)
-                .ok()?;
-            self.index = 0;
         }
         let n = self.buffer[self.index];
         self.index += 1;
         Some(n)
     }
+}
 
-    #[cfg(test)]
-    fn next(&mut self) -> Option<Self::Item> {
-        // If at end of buffer
+/// Reads straight out of the internal buffer, so raw reads can be mixed with the parsing methods
+impl<R: Read> Read for Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = buf.len().min(available.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+/// Exposes the internal buffer, so std helpers like `read_line` can be used for unusual formats
+///
+/// # Example:
+///
+/// ```
+/// use std::io::BufRead;
+///
+/// let mut reader = comp_io::Reader::from_str("2\nfree form line\n");
+/// assert_eq!(reader.next_usize(), Some(2));
+/// let mut line = String::new();
+/// reader.read_line(&mut line).unwrap();
+/// assert_eq!(line, "free form line\n");
+/// ```
+impl<R: Read> BufRead for Reader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if let Some(last) = self.pushback.len().checked_sub(1) {
+            return Ok(&self.pushback[last..]);
+        }
         if self.index >= self.len {
-            return None;
+            self.refill(self.len);
         }
-        let n = self.buffer[self.index];
-        self.index += 1;
-        Some(n)
+        Ok(&self.buffer[self.index..self.len])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        // `fill_buf` hands out pushed back bytes one at a time
+        if amount > 0 && self.pop_pushback().is_some() {
+            return;
+        }
+        self.index = (self.index + amount).min(self.len);
+    }
+}
+
+/// A saved place in an in-memory reader, returned by `Reader::mark` and rewound to by `Reader::rollback`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mark(usize);
+
+/// A line and column in the input, both starting at 1, as returned by `Reader::line_col`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, col {}", self.line, self.column)
+    }
+}
+
+/// An iterator over the whitespace-delimited tokens of an in-memory reader's input, created by `Reader::tokens`
+#[derive(Debug, Clone)]
+pub struct Tokens<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.rest.iter().position(|c| !c.is_ascii_whitespace())?;
+        let rest = &self.rest[start..];
+        let end = rest.iter().position(u8::is_ascii_whitespace).unwrap_or(rest.len());
+        let (token, rest) = rest.split_at(end);
+        self.rest = rest;
+        Some(token)
+    }
+}
+
+/// An iterator over blank-line separated blocks of lines, created by `Reader::blocks`
+pub struct Blocks<'a, R> {
+    reader: &'a mut Reader<R>,
+}
+
+impl<R: Read> Iterator for Blocks<'_, R> {
+    type Item = Vec<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader.read_until_blank_line()
+    }
+}
+
+/// Generates a parser for a signed integer type, accumulating directly into that type
+///
+/// Returns the value together with the byte that terminated it. Digits are accumulated as a
+/// negative number, since the negative range is one larger, so `MIN` parses without overflow
+macro_rules! read_signed {
+    ($name:ident, $t:ty) => {
+        fn $name(&mut self) -> Option<($t, u8)> {
+            let mut r: $t = 0;
+            let (mut val, neg) = match self.value_start()? {
+                v @ b'0'..=b'9' => (v, false), // could also move to the end with no ifs, don't know which is better
+                b'-' => (b'0', true),
+                b'+' => (b'0', false),
+                b => {
+                    // Unexpected character
+                    self.fail(ReadErrorKind::UnexpectedByte(b), Some(b));
+                    return None;
+                }
+            };
+
+            while val.is_ascii_digit() {
+                r = r * 10 - (val - b'0') as $t;
+
+                val = match self.next() {
+                    Some(a) => a,
+                    None => break,
+                };
+            }
+            self.consume_crlf(Some(val));
+            Some((if neg { r } else { -r }, val))
+        }
+    };
+}
+
+/// Generates a parser for an unsigned integer type. A leading sign is dropped, same as `next_u32`
+macro_rules! read_unsigned {
+    ($name:ident, $t:ty) => {
+        fn $name(&mut self) -> Option<$t> {
+            let mut r: $t = 0;
+            let mut val = match self.value_start()? {
+                v @ b'0'..=b'9' => v,
+                b'-' | b'+' => b'0',
+                b => {
+                    // Unexpected character
+                    self.fail(ReadErrorKind::UnexpectedByte(b), Some(b));
+                    return None;
+                }
+            };
+
+            while val.is_ascii_digit() {
+                r = r * 10 + (val - b'0') as $t;
+
+                val = match self.next() {
+                    Some(a) => a,
+                    None => break,
+                };
+            }
+            self.consume_crlf(Some(val));
+            Some(r)
+        }
+    };
+}
+
+/// Generates a `try_next_*` name for one of the checked parsers
+macro_rules! try_alias {
+    ($name:ident, $checked:ident, $t:ty) => {
+        #[doc = concat!("Reads the next ", stringify!($t), " from stdin, same as `", stringify!($checked), "`")]
+        pub fn $name(&mut self) -> Result<$t, ReadError> {
+            self.$checked()
+        }
+    };
+}
+
+/// Generates a read that panics with the error and surrounding input instead of returning a `Result`
+macro_rules! expect_read {
+    ($name:ident, $try:ident, $t:ty) => {
+        #[doc = concat!("Reads the next ", stringify!($t), " from stdin, panicking with the position and nearby input if it fails")]
+        #[track_caller]
+        pub fn $name(&mut self) -> $t {
+            match self.$try() {
+                Ok(value) => value,
+                Err(error) => self.read_panic(stringify!($t), error),
+            }
+        }
+    };
+}
+
+/// Generates an overflow-checked parser for a signed integer type
+macro_rules! checked_signed {
+    ($name:ident, $t:ty) => {
+        #[doc = concat!("Reads the next ", stringify!($t), " from stdin, returning an error instead of wrapping if it doesn't fit")]
+        pub fn $name(&mut self) -> Result<$t, ReadError> {
+            let (mut val, neg) = match self.value_start() {
+                Some(v @ b'0'..=b'9') => (v, false),
+                Some(b'-') => (b'0', true),
+                Some(b'+') => (b'0', false),
+                Some(b) => {
+                    self.fail(ReadErrorKind::UnexpectedByte(b), Some(b));
+                    return Err(self.error(ReadErrorKind::UnexpectedByte(b)));
+                }
+                None => return Err(self.error(ReadErrorKind::Eof)),
+            };
+
+            // keep consuming digits after an overflow so the whole token is skipped
+            let mut r: Option<$t> = Some(0);
+            while val.is_ascii_digit() {
+                r = r.and_then(|r| r.checked_mul(10)?.checked_sub((val - b'0') as $t));
+
+                val = match self.next() {
+                    Some(a) => a,
+                    None => break,
+                };
+            }
+            self.consume_crlf(Some(val));
+            let r = match r {
+                Some(r) if neg => Some(r),
+                Some(r) => r.checked_neg(),
+                None => None,
+            };
+            r.ok_or_else(|| {
+                self.fail(ReadErrorKind::Overflow, Some(val));
+                self.error(ReadErrorKind::Overflow)
+            })
+        }
+    };
+}
+
+/// Generates an overflow-checked parser for an unsigned integer type. Negative values other than `-0` overflow
+macro_rules! checked_unsigned {
+    ($name:ident, $t:ty) => {
+        #[doc = concat!("Reads the next ", stringify!($t), " from stdin, returning an error instead of wrapping if it doesn't fit")]
+        pub fn $name(&mut self) -> Result<$t, ReadError> {
+            let (mut val, neg) = match self.value_start() {
+                Some(v @ b'0'..=b'9') => (v, false),
+                Some(b'-') => (b'0', true),
+                Some(b'+') => (b'0', false),
+                Some(b) => {
+                    self.fail(ReadErrorKind::UnexpectedByte(b), Some(b));
+                    return Err(self.error(ReadErrorKind::UnexpectedByte(b)));
+                }
+                None => return Err(self.error(ReadErrorKind::Eof)),
+            };
+
+            let mut r: Option<$t> = Some(0);
+            while val.is_ascii_digit() {
+                r = r.and_then(|r| r.checked_mul(10)?.checked_add((val - b'0') as $t));
+
+                val = match self.next() {
+                    Some(a) => a,
+                    None => break,
+                };
+            }
+            self.consume_crlf(Some(val));
+            match r {
+                Some(0) => Ok(0),
+                Some(r) if !neg => Ok(r),
+                _ => {
+                    self.fail(ReadErrorKind::Overflow, Some(val));
+                    Err(self.error(ReadErrorKind::Overflow))
+                }
+            }
+        }
+    };
+}
+
+/// The reason a checked read failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadErrorKind {
+    /// The input ended before a value was found
+    Eof,
+    /// The value started with a byte that can't begin it
+    UnexpectedByte(u8),
+    /// The value doesn't fit in the requested type
+    Overflow,
+    /// The value is outside the bounds it was read with, see `Reader::next_i64_in`
+    OutOfRange,
+    /// The value started out fine but isn't well-formed, such as `1e` for a float or a token that fails to parse
+    Invalid,
+}
+
+impl fmt::Display for ReadErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadErrorKind::Eof => write!(f, "unexpected end of input"),
+            ReadErrorKind::UnexpectedByte(b) => write!(f, "unexpected byte {:?}", *b as char),
+            ReadErrorKind::Overflow => write!(f, "number too large for target type"),
+            ReadErrorKind::OutOfRange => write!(f, "value out of range"),
+            ReadErrorKind::Invalid => write!(f, "malformed value"),
+        }
+    }
+}
+
+/// A failed read, returned by the `checked_next_*` and `try_next_*` methods
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::ReadErrorKind;
+///
+/// let mut reader = comp_io::Reader::from_str("1\n99999999999");
+/// assert_eq!(reader.try_next_i32(), Ok(1));
+/// let error = reader.try_next_i32().unwrap_err();
+/// assert_eq!(error.kind, ReadErrorKind::Overflow);
+/// assert_eq!(error.to_string(), "number too large for target type at line 2, col 12");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadError {
+    pub kind: ReadErrorKind,
+    /// Where reading stopped, just after the offending byte
+    pub position: Position,
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.kind, self.position)
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+impl Default for Reader {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl Reader {
     /// Instantiates a new reader
     ///
+    /// Stdin is locked once here and stays locked for the reader's lifetime, rather than on every refill
+    ///
     /// # Example:
     ///
     /// ```
     /// let mut reader = comp_io::Reader::new();
     /// ```
     pub fn new() -> Self {
-        Reader {
-            buffer: Vec::<u8>::with_capacity(400_000),
-            index: usize::MAX,
-            len: usize::MAX,
-        }
+        Reader::with_source(io::stdin().lock(), Vec::<u8>::with_capacity(400_000))
+    }
+
+    /// Starts configuring a reader, see `ReaderBuilder`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use comp_io::{Reader, Whitespace};
+    ///
+    /// let input = std::io::Cursor::new("  1\n\n  -2");
+    /// let mut reader = Reader::builder().capacity(1 << 20).whitespace(Whitespace::Lenient).source(input);
+    /// assert_eq!(reader.next_pair(), Some((1, -2)));
+    /// ```
+    pub fn builder() -> ReaderBuilder {
+        ReaderBuilder::default()
+    }
+}
+
+impl Reader<Box<dyn Read>> {
+    /// Instantiates a reader over a local input file if there is one, and stdin otherwise
+    ///
+    /// The file is the path in the `COMP_IO_INPUT` environment variable if it is set, or else `./input.txt`
+    /// if it exists. This lets the same source run locally against saved tests and on the judge unchanged
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `COMP_IO_INPUT` is set but the file can't be opened
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// let mut reader = comp_io::Reader::auto();
+    /// let n = reader.next_usize().unwrap();
+    /// ```
+    pub fn auto() -> Self {
+        let source: Box<dyn Read> = match env::var_os("COMP_IO_INPUT") {
+            Some(path) => match File::open(&path) {
+                Ok(file) => Box::new(file),
+                Err(e) => panic!("couldn't open COMP_IO_INPUT file {path:?}: {e}"),
+            },
+            None => match File::open("input.txt") {
+                Ok(file) => Box::new(file),
+                Err(_) => Box::new(io::stdin().lock()),
+            },
+        };
+        Reader::from_reader(source)
+    }
+}
+
+impl Reader<File> {
+    /// Instantiates a reader that buffers input from the file at `path`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # let path = std::env::temp_dir().join("comp_io_from_file.txt");
+    /// # std::fs::write(&path, "3\n1 2 3\n").unwrap();
+    /// let mut reader = comp_io::Reader::from_file(&path).unwrap();
+    /// let n = reader.next_usize().unwrap();
+    /// let a: Vec<i32> = reader.next_vec(n).unwrap();
+    /// assert_eq!(a, vec![1, 2, 3]);
+    /// ```
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Reader::from_reader(File::open(path)?))
+    }
+}
+
+impl Reader<ChildStdout> {
+    /// Instantiates a reader over the stdout of a spawned process, taking the handle out of `child`
+    ///
+    /// Returns `None` if the child's stdout wasn't piped or was already taken
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use std::process::{Command, Stdio};
+    ///
+    /// let mut judge = Command::new("./judge").stdout(Stdio::piped()).spawn().unwrap();
+    /// let mut reader = comp_io::Reader::from_child(&mut judge).unwrap();
+    /// let n = reader.next_usize().unwrap();
+    /// ```
+    pub fn from_child(child: &mut Child) -> Option<Self> {
+        child.stdout.take().map(Reader::from_reader)
     }
+}
 
+impl Reader<io::Empty> {
     /// Useful for testing reader without requiring access to stdin
     ///
-    /// Note: Best with `cargo test`, otherwise still needs stdin
+    /// Only `input` is ever read, so the reader behaves the same in tests and in release builds
     ///
     /// # Example:
     ///
     /// ```
     /// let mut reader = comp_io::Reader::from_str("12 43\n-42");
     /// ```
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(input: &str) -> Self {
-        Reader {
-            buffer: input.as_bytes().to_vec(),
-            index: 0,
-            len: input.len(),
-        }
+        Reader::from(input)
     }
 
-    fn read_i32(&mut self) -> Option<(i32, i32)> {
-        // let (mut r, mut val, neg) = (0, 48, self.next()? == b'-');
-        // self.index -= if neg {0} else {1};
+    /// Reads all of stdin into memory before any parsing, so the input is never refilled while reading
+    ///
+    /// Not for interactive problems, since it waits for the input to end. Since the whole input stays in memory,
+    /// `tokens` can then borrow tokens from it that stay valid as long as the reader does
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// let mut reader = comp_io::Reader::slurp();
+    /// let n = reader.next_usize().unwrap();
+    /// let names: Vec<&[u8]> = reader.tokens().take(n).collect();
+    /// ```
+    pub fn slurp() -> Self {
+        let mut input = Vec::with_capacity(400_000);
+        // like a refill, an error just ends the input
+        let _ = io::stdin().lock().read_to_end(&mut input);
+        Reader::from(input)
+    }
 
-        let mut r = 0;
-        let (mut val, neg) = match self.next()? {
-            v @ b'0'..=b'9' => (v as i32, false), // could also move to the end with no ifs, don't know which is better
-            b'-' => (48, true),
-            b'+' => (48, false),
-            _ => return None, // Unexpected character
-        };
+    /// Returns the unread rest of the input, borrowed from the reader instead of copied
+    ///
+    /// Bytes handed back by `unread` that differ from the input are not included
+    pub fn remaining(&self) -> &[u8] {
+        &self.buffer[self.index..self.len]
+    }
 
-        while val >= b'0' as i32 && val <= b'9' as i32 {
-            r = r * 10 + val - 48;
+    /// Returns an iterator over the whitespace-delimited tokens in the unread rest of the input
+    ///
+    /// The tokens borrow the reader instead of `&mut` like `next_token_bytes`, so as many of them can be kept at once
+    /// as needed. The reader itself doesn't move past them, `skip_tokens` does that
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let reader = comp_io::Reader::from_str("b a\nb");
+    /// let mut tokens: Vec<&[u8]> = reader.tokens().collect();
+    /// tokens.sort();
+    /// assert_eq!(tokens, [&b"a"[..], b"b", b"b"]);
+    /// ```
+    pub fn tokens(&self) -> Tokens<'_> {
+        Tokens { rest: self.remaining() }
+    }
 
-            val = match self.next() {
-                Some(a) => a as i32,
-                None => break,
-            };
-        }
-        Some((if neg { -r } else { r }, val))
+    /// Saves the current place in the input, so a speculative parse can be undone with `rollback`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("abc 5");
+    /// let mark = reader.mark();
+    /// if reader.next_i32().is_none() {
+    ///     reader.rollback(mark);
+    ///     assert_eq!(reader.next_token().unwrap(), "abc");
+    /// }
+    /// ```
+    pub fn mark(&self) -> Mark {
+        Mark(self.index)
     }
 
-    /// Reads the next u32 from stdin
-    pub fn next_u32(&mut self) -> Option<u32> {
-        Some(self.read_i32()?.0.unsigned_abs())
+    /// Rewinds to a place saved by `mark`, so everything read since is read again
+    pub fn rollback(&mut self, mark: Mark) {
+        self.clear_pushback();
+        self.index = mark.0;
     }
 
-    /// Reads the next usize from stdin
-    pub fn next_usize(&mut self) -> Option<usize> {
-        Some(self.read_i32()?.0.unsigned_abs() as usize)
+    /// Rewinds to the start of the input, so the same reader can be parsed again
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1 2");
+    /// for _ in 0..3 {
+    ///     reader.reset();
+    ///     assert_eq!(reader.next_pair(), Some((1, 2)));
+    /// }
+    /// ```
+    pub fn reset(&mut self) {
+        self.clear_pushback();
+        self.index = 0;
     }
 
-    /// Reads the next i32 from stdin
-    pub fn next_i32(&mut self) -> Option<i32> {
-        Some(self.read_i32()?.0)
+    /// Returns the offset of the next unread byte from the start of the input
+    pub fn byte_pos(&self) -> usize {
+        self.index
     }
 
-    /// Reads the next char from stdin
-    pub fn next_char(&mut self) -> Option<char> {
-        Some(self.next()? as char)
+    /// Moves to byte offset `pos` of the input, so it can be parsed again without rebuilding the reader
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `pos` is past the end of the input
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("2 5 7");
+    /// let n = reader.next_usize().unwrap();
+    /// let data = reader.byte_pos();
+    /// let sum: i32 = (0..n).map(|_| reader.next_i32().unwrap()).sum();
+    /// reader.seek(data);
+    /// assert_eq!(reader.next_vec::<i32>(n), Some(vec![5, 7]));
+    /// assert_eq!(sum, 12);
+    /// ```
+    pub fn seek(&mut self, pos: usize) {
+        assert!(pos <= self.len, "seek to {pos} is past the end of the input ({} bytes)", self.len);
+        self.clear_pushback();
+        self.index = pos;
     }
+}
 
-    /// Reads the next pair of i32s from stdin
-    pub fn next_pair(&mut self) -> Option<(i32, i32)> {
-        Some((self.read_i32()?.0, self.read_i32()?.0))
+/// Takes ownership of the bytes without copying them
+impl From<Vec<u8>> for Reader<io::Empty> {
+    fn from(input: Vec<u8>) -> Self {
+        let mut reader = Reader::with_source(io::empty(), input);
+        // the whole input is already in the buffer, so it must never be refilled
+        reader.exhausted = true;
+        reader
     }
+}
 
-    /// Reads the next f64 from stdin
-    pub fn next_f64(&mut self) -> Option<f64> {
-        let (base, latest) = self.read_i32()?;
-        let base: f64 = base as f64;
-        if latest != b'.' as i32 { // number doesn't have a period
-            return Some(base);
-        }
-        let (mut dec, mut val, mut ten) = (0.0, 48, 1.0);
-        while val >= b'0' && val <= b'9' {
-            dec += ((val - b'0') as f64) * ten;
-            ten *= 0.1;
-            val = match self.next() {
-                Some(a) => a,
-                _ => break,
-            };
-        }
-        Some(base + dec.copysign(base))
+/// Takes ownership of the string without copying it
+impl From<String> for Reader<io::Empty> {
+    fn from(input: String) -> Self {
+        Reader::from(input.into_bytes())
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl From<&[u8]> for Reader<io::Empty> {
+    fn from(input: &[u8]) -> Self {
+        Reader::from(input.to_vec())
+    }
+}
 
-    #[test]
-    fn test_reader_from_str() {
-        let reader = Reader::from_str("-4.1");
+impl From<&str> for Reader<io::Empty> {
+    fn from(input: &str) -> Self {
+        Reader::from(input.as_bytes())
+    }
+}
 
-        assert_eq!(reader.buffer, vec![45, 52, 46, 49]);
-        assert_eq!(reader.index, 0);
-        assert_eq!(reader.len, 4);
+impl<R: Read> Reader<R> {
+    /// Instantiates a reader over any source implementing `Read`, such as a `TcpStream`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let input = std::io::Cursor::new("7 -3");
+    /// let mut reader = comp_io::Reader::from_reader(input);
+    /// assert_eq!(reader.next_pair(), Some((7, -3)));
+    /// ```
+    pub fn from_reader(source: R) -> Self {
+        Reader::with_source(source, Vec::with_capacity(400_000))
     }
 
-    #[test]
-    fn test_next_f64() {
-        let mut reader = Reader::from_str("-4 45 -754.3 32. 45");
-        assert_eq!(reader.next_f64().unwrap(), -4.);
-        assert_eq!(reader.next_f64().unwrap(), 45.);
-        assert_eq!(reader.next_f64().unwrap(), -754.3);
-        assert_eq!(reader.next_f64().unwrap(), 32.);
-        assert_eq!(reader.next_f64().unwrap(), 45.);
+    /// Instantiates a reader over `source`, with `buffer` holding input that was already read from it
+    fn with_source(source: R, buffer: Vec<u8>) -> Self {
+        let len = buffer.len();
+        Reader {
+            source,
+            index: 0,
+            len,
+            buffer: Buffer::from(buffer),
+            scratch: Vec::new(),
+            decimal_separator: b'.',
+            exhausted: false,
+            interactive: false,
+            capacity: 400_000,
+            whitespace: Whitespace::Single,
+            crlf: true,
+            resync: false,
+            failure: None,
+            discarded_lines: 0,
+            discarded_column: 0,
+            discarded_bytes: 0,
+            pushback: Vec::new(),
+            fast_len: len,
+        }
     }
 
-    #[test]
-    fn test_next_char() {
-        let mut reader = Reader::from_str("ab cd");
-        assert_eq!(reader.next_char().unwrap(), 'a');
-        assert_eq!(reader.next_char().unwrap(), 'b');
-        assert_eq!(reader.next_char().unwrap(), ' ');
-        assert_eq!(reader.next_char().unwrap(), 'c');
-        assert_eq!(reader.next_char().unwrap(), 'd');
+    /// Continues reading from `next` once the current source runs out
+    ///
+    /// Input that was already buffered is kept, so a fixed header can be put in front of stdin, or several
+    /// files can be read back to back. Sources are joined as-is, so each should end with a delimiter
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1\n").chain(&b"5 6"[..]);
+    /// assert_eq!(reader.next_i32(), Some(1));
+    /// assert_eq!(reader.next_pair(), Some((5, 6)));
+    /// ```
+    pub fn chain<S: Read>(self, next: S) -> Reader<io::Chain<R, S>> {
+        let mut reader = self.map_source(|source| source.chain(next));
+        reader.exhausted = false;
+        reader
     }
 
-    #[test]
-    fn test_next_pair() {
-        let mut reader = Reader::from_str("23 32\n12 -34 57 97\n-12 3");
-        assert_eq!(reader.next_pair().unwrap(), (23, 32));
-        assert_eq!(reader.next_pair().unwrap(), (12, -34));
-        assert_eq!(reader.next_pair().unwrap(), (57, 97));
-        assert_eq!(reader.next_pair().unwrap(), (-12, 3));
+    /// Swaps the source for `f(source)`, keeping the buffered input and all settings
+    pub(crate) fn map_source<S: Read>(self, f: impl FnOnce(R) -> S) -> Reader<S> {
+        Reader {
+            source: f(self.source),
+            buffer: self.buffer,
+            index: self.index,
+            len: self.len,
+            scratch: self.scratch,
+            decimal_separator: self.decimal_separator,
+            exhausted: self.exhausted,
+            interactive: self.interactive,
+            capacity: self.capacity,
+            whitespace: self.whitespace,
+            crlf: self.crlf,
+            resync: self.resync,
+            failure: self.failure,
+            discarded_lines: self.discarded_lines,
+            discarded_column: self.discarded_column,
+            discarded_bytes: self.discarded_bytes,
+            pushback: self.pushback,
+            fast_len: self.fast_len,
+        }
     }
 
-    #[test]
+    /// Reads more input from the source, moving the bytes from `keep_from` onwards to the front of the buffer
+    ///
+    /// Returns `false` if nothing more could be read
+    fn refill(&mut self, keep_from: usize) -> bool {
+        if self.exhausted {
+            return false;
+        }
+        self.discard(keep_from);
+        let start = self.len;
+        let end = start + self.capacity;
+        let buffer = self.buffer.vec_mut();
+        // the space past `len` is kept between refills, so it only has to be zeroed the first time
+        if buffer.len() < end {
+            buffer.resize(end, 0);
+        }
+        let mut filled = start;
+        // pipes and sockets can return short reads mid-stream, so only a read of 0 bytes means the input ended
+        while filled < end {
+            match self.source.read(&mut buffer[filled..end]) {
+                Ok(0) => {
+                    self.exhausted = true;
+                    break;
+                }
+                Ok(n) => {
+                    filled += n;
+                    if self.interactive {
+                        break;
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) => {
+                    self.exhausted = true;
+                    break;
+                }
+            }
+        }
+        self.len = filled;
+        self.update_fast_len();
+        filled > start
+    }
+
+    /// Drops the first `n` bytes of the buffer, remembering how many lines they contained for `line_col`
+    fn discard(&mut self, n: usize) {
+        let dropped = &self.buffer[..n];
+        match dropped.iter().rposition(|&c| c == b'\n') {
+            Some(last) => {
+                self.discarded_lines += dropped.iter().filter(|&&c| c == b'\n').count();
+                self.discarded_column = n - last - 1;
+            }
+            None => self.discarded_column += n,
+        }
+        self.discarded_bytes += n;
+        self.buffer.vec_mut().copy_within(n..self.len, 0);
+        self.index -= n;
+        self.len -= n;
+        self.update_fast_len();
+    }
+
+    /// Returns the line and column of the next unread byte, both starting at 1
+    ///
+    /// The lines still in the buffer are only counted when this is called. Bytes a refill drops from the buffer are
+    /// counted once as they are dropped, so tracking costs one pass over each refilled chunk
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1 2\n3 x");
+    /// reader.skip_tokens(3);
+    /// let position = reader.line_col();
+    /// assert_eq!((position.line, position.column), (2, 3));
+    /// assert_eq!(position.to_string(), "line 2, col 3");
+    /// ```
+    pub fn line_col(&self) -> Position {
+        self.line_col_at(self.index)
+    }
+
+    /// Returns the line and column of the byte at `index` in the buffer
+    fn line_col_at(&self, index: usize) -> Position {
+        let read = &self.buffer[..index.min(self.len)];
+        match read.iter().rposition(|&c| c == b'\n') {
+            Some(last) => Position {
+                line: self.discarded_lines + read.iter().filter(|&&c| c == b'\n').count() + 1,
+                column: read.len() - last,
+            },
+            None => Position {
+                line: self.discarded_lines + 1,
+                column: self.discarded_column + read.len() + 1,
+            },
+        }
+    }
+
+    /// Sets the byte `next_f64` treats as the decimal point, e.g. `b','` for `3,14`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("3,14").with_decimal_separator(b',');
+    /// assert_eq!(reader.next_f64(), Some(3.14));
+    /// ```
+    pub fn with_decimal_separator(mut self, separator: u8) -> Self {
+        self.decimal_separator = separator;
+        self
+    }
+
+    /// Makes refills return whatever input is currently available instead of waiting for a full buffer,
+    /// so query/response problems don't deadlock waiting on input that is only sent after the next answer
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// let mut reader = comp_io::Reader::new().interactive();
+    /// let n = reader.next_usize().unwrap();
+    /// println!("? 1");
+    /// let reply = reader.next_i32().unwrap();
+    /// ```
+    pub fn interactive(mut self) -> Self {
+        self.interactive = true;
+        self
+    }
+
+    /// Makes a failed number read also skip the rest of the bad token, so parsing picks up at the next one.
+    /// Useful in checkers reading output that may be garbage
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("x7z 3").with_resync();
+    /// assert_eq!(reader.next_i32(), None);
+    /// assert_eq!(reader.next_i32(), Some(3));
+    /// ```
+    pub fn with_resync(mut self) -> Self {
+        self.resync = true;
+        self
+    }
+
+    read_signed!(read_i32, i32);
+    read_signed!(read_i64, i64);
+    read_signed!(read_i128, i128);
+
+    read_unsigned!(read_u32, u32);
+    read_unsigned!(read_usize, usize);
+    read_unsigned!(read_u64, u64);
+    read_unsigned!(read_u128, u128);
+
+    /// Reads the next u32 from stdin
+    pub fn next_u32(&mut self) -> Option<u32> {
+        self.read_u32()
+    }
+
+    /// Reads the next usize from stdin
+    pub fn next_usize(&mut self) -> Option<usize> {
+        self.read_usize()
+    }
+
+    /// Reads the next i32 from stdin
+    pub fn next_i32(&mut self) -> Option<i32> {
+        Some(self.read_i32()?.0)
+    }
+
+    /// Reads the next i64 from stdin
+    pub fn next_i64(&mut self) -> Option<i64> {
+        Some(self.read_i64()?.0)
+    }
+
+    /// Reads the next u64 from stdin
+    pub fn next_u64(&mut self) -> Option<u64> {
+        self.read_u64()
+    }
+
+    /// Reads the next i128 from stdin
+    pub fn next_i128(&mut self) -> Option<i128> {
+        Some(self.read_i128()?.0)
+    }
+
+    /// Reads the next u128 from stdin
+    pub fn next_u128(&mut self) -> Option<u128> {
+        self.read_u128()
+    }
+
+    checked_signed!(checked_next_i8, i8);
+    checked_signed!(checked_next_i16, i16);
+    checked_signed!(checked_next_i32, i32);
+    checked_signed!(checked_next_i64, i64);
+    checked_signed!(checked_next_i128, i128);
+
+    checked_unsigned!(checked_next_u8, u8);
+    checked_unsigned!(checked_next_u16, u16);
+    checked_unsigned!(checked_next_u32, u32);
+    checked_unsigned!(checked_next_usize, usize);
+    checked_unsigned!(checked_next_u64, u64);
+    checked_unsigned!(checked_next_u128, u128);
+
+    try_alias!(try_next_i32, checked_next_i32, i32);
+    try_alias!(try_next_i64, checked_next_i64, i64);
+    try_alias!(try_next_u32, checked_next_u32, u32);
+    try_alias!(try_next_u64, checked_next_u64, u64);
+    try_alias!(try_next_usize, checked_next_usize, usize);
+
+    expect_read!(i32, try_next_i32, i32);
+    expect_read!(i64, try_next_i64, i64);
+    expect_read!(u32, try_next_u32, u32);
+    expect_read!(u64, try_next_u64, u64);
+    expect_read!(usize, try_next_usize, usize);
+    expect_read!(f64, try_next_f64, f64);
+    expect_read!(token, try_next_token, String);
+
+    /// Panics for a failed `expect_read!` read, showing the input around where it stopped
+    #[track_caller]
+    fn read_panic(&self, what: &str, error: ReadError) -> ! {
+        let start = self.index.saturating_sub(16);
+        let end = (self.index + 16).min(self.len);
+        let near = String::from_utf8_lossy(&self.buffer[start..end]);
+        panic!("couldn't read {what}: {error}, near {near:?}");
+    }
+
+    /// Tells why the last read returned `None`: malformed input, or `Eof` if the input has run out
+    ///
+    /// Returns `None` if the last read succeeded and there is input left
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use comp_io::ReadErrorKind;
+    ///
+    /// let mut reader = comp_io::Reader::from_str("1 x");
+    /// assert_eq!(reader.next_i32(), Some(1));
+    /// assert_eq!(reader.last_error(), None);
+    /// assert_eq!(reader.next_i32(), None);
+    /// assert_eq!(reader.last_error(), Some(ReadErrorKind::UnexpectedByte(b'x')));
+    /// assert_eq!(reader.next_i32(), None);
+    /// assert_eq!(reader.last_error(), Some(ReadErrorKind::Eof));
+    /// ```
+    pub fn last_error(&mut self) -> Option<ReadErrorKind> {
+        if self.failure.is_some() {
+            return self.failure;
+        }
+        self.peek().is_none().then_some(ReadErrorKind::Eof)
+    }
+
+    /// Builds an error of `kind` at the current position
+    fn error(&self, kind: ReadErrorKind) -> ReadError {
+        self.error_at(kind, self.offset())
+    }
+
+    /// Builds an error of `kind` at the byte at `offset` as returned by `offset`, which has to still be in the buffer
+    fn error_at(&self, kind: ReadErrorKind, offset: usize) -> ReadError {
+        ReadError {
+            kind,
+            position: self.line_col_at(offset.saturating_sub(self.discarded_bytes)),
+        }
+    }
+
+    /// Returns the offset of the next unread byte from the start of the input
+    ///
+    /// Unlike the buffer index, it stays the same when a refill drops bytes from the front of the buffer
+    pub(crate) fn offset(&self) -> usize {
+        self.discarded_bytes + self.index
+    }
+
+    /// Peeks the byte a value would start at, skipping whitespace first in lenient mode
+    fn peek_value_start(&mut self) -> Option<u8> {
+        if self.whitespace == Whitespace::Lenient && !self.has_next() {
+            return None;
+        }
+        self.peek()
+    }
+
+    /// Reads the next i64 from stdin, returning an error if it isn't in `range`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use comp_io::ReadErrorKind;
+    ///
+    /// let mut reader = comp_io::Reader::from_str("5 0");
+    /// assert_eq!(reader.next_i64_in(1..=100_000), Ok(5));
+    /// assert_eq!(reader.next_i64_in(1..=100_000).unwrap_err().kind, ReadErrorKind::OutOfRange);
+    /// ```
+    pub fn next_i64_in(&mut self, range: impl RangeBounds<i64>) -> Result<i64, ReadError> {
+        let value = self.checked_next_i64()?;
+        if range.contains(&value) {
+            Ok(value)
+        } else {
+            self.fail(ReadErrorKind::OutOfRange, None);
+            Err(self.error(ReadErrorKind::OutOfRange))
+        }
+    }
+
+    /// Same as `next_f64`, returning why the read failed
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use comp_io::ReadErrorKind;
+    ///
+    /// let mut reader = comp_io::Reader::from_str("2.5 1e x");
+    /// assert_eq!(reader.try_next_f64(), Ok(2.5));
+    /// assert_eq!(reader.try_next_f64().unwrap_err().kind, ReadErrorKind::Invalid);
+    /// assert_eq!(reader.try_next_f64().unwrap_err().kind, ReadErrorKind::UnexpectedByte(b'x'));
+    /// assert_eq!(reader.try_next_f64().unwrap_err().kind, ReadErrorKind::Eof);
+    /// ```
+    pub fn try_next_f64(&mut self) -> Result<f64, ReadError> {
+        let start = self.peek_value_start();
+        self.next_f64().ok_or_else(|| {
+            self.error(match start {
+                None => ReadErrorKind::Eof,
+                Some(b'0'..=b'9' | b'-' | b'+' | b'i' | b'I' | b'n' | b'N') => ReadErrorKind::Invalid,
+                Some(b) if b == self.decimal_separator => ReadErrorKind::Invalid,
+                Some(b) => ReadErrorKind::UnexpectedByte(b),
+            })
+        })
+    }
+
+    /// Same as `next_token`, returning an error at the end of input or if the token is not valid UTF-8
+    pub fn try_next_token(&mut self) -> Result<String, ReadError> {
+        match self.next_token_bytes().map(|token| String::from_utf8(token.to_vec())) {
+            Some(Ok(token)) => Ok(token),
+            Some(Err(_)) => {
+                self.failure = Some(ReadErrorKind::Invalid);
+                Err(self.error(ReadErrorKind::Invalid))
+            }
+            None => Err(self.error(ReadErrorKind::Eof)),
+        }
+    }
+
+    /// Same as `next_parse`, returning an error at the end of input or if the token fails to parse
+    pub fn try_next_parse<T: FromStr>(&mut self) -> Result<T, ReadError> {
+        match self.next_token_str().map(str::parse) {
+            Some(Ok(value)) => Ok(value),
+            Some(Err(_)) => {
+                self.failure = Some(ReadErrorKind::Invalid);
+                Err(self.error(ReadErrorKind::Invalid))
+            }
+            None => Err(self.error(ReadErrorKind::Eof)),
+        }
+    }
+
+    /// Reads the next u8 from stdin, or `None` if it is out of range
+    pub fn next_u8(&mut self) -> Option<u8> {
+        self.checked_next_u8().ok()
+    }
+
+    /// Reads the next i8 from stdin, or `None` if it is out of range
+    pub fn next_i8(&mut self) -> Option<i8> {
+        self.checked_next_i8().ok()
+    }
+
+    /// Reads the next u16 from stdin, or `None` if it is out of range
+    pub fn next_u16(&mut self) -> Option<u16> {
+        self.checked_next_u16().ok()
+    }
+
+    /// Reads the next i16 from stdin, or `None` if it is out of range
+    pub fn next_i16(&mut self) -> Option<i16> {
+        self.checked_next_i16().ok()
+    }
+
+    /// Reads the next char from stdin
+    ///
+    /// A `\r\n` line ending is read as a single `\n`
+    pub fn next_char(&mut self) -> Option<char> {
+        self.failure = None;
+        let c = self.next()?;
+        if c == b'\r' && self.peek() == Some(b'\n') {
+            self.next();
+            return Some('\n');
+        }
+        Some(c as char)
+    }
+
+    /// Reads the next boolean from stdin. Accepts `0`/`1`, `true`/`false` and `yes`/`no`, ignoring case
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("YES 0 False");
+    /// assert_eq!(reader.next_bool(), Some(true));
+    /// assert_eq!(reader.next_bool(), Some(false));
+    /// assert_eq!(reader.next_bool(), Some(false));
+    /// ```
+    pub fn next_bool(&mut self) -> Option<bool> {
+        self.scratch.clear();
+        let mut val = self.value_start();
+        while let Some(c) = val.filter(u8::is_ascii_alphanumeric) {
+            self.scratch.push(c.to_ascii_lowercase());
+            val = self.next();
+        }
+        self.consume_crlf(val);
+        match &self.scratch[..] {
+            b"1" | b"true" | b"yes" => Some(true),
+            b"0" | b"false" | b"no" => Some(false),
+            b"" if val.is_none() => None,
+            _ => {
+                self.fail(ReadErrorKind::Invalid, val);
+                None
+            }
+        }
+    }
+
+    /// Reads the next whitespace-delimited token from stdin, skipping any leading whitespace
+    ///
+    /// Returns `None` at the end of input, or if the token is not valid UTF-8
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("  hello\nworld");
+    /// assert_eq!(reader.next_token().unwrap(), "hello");
+    /// assert_eq!(reader.next_token().unwrap(), "world");
+    /// ```
+    pub fn next_token(&mut self) -> Option<String> {
+        self.next_token_str().map(str::to_owned)
+    }
+
+    /// Reads the next whitespace-delimited token from stdin without copying it out of the internal buffer
+    ///
+    /// The slice borrows the reader, so it must be dropped before the next read
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("ab cd");
+    /// assert_eq!(reader.next_token_bytes(), Some(&b"ab"[..]));
+    /// assert_eq!(reader.next_token_bytes(), Some(&b"cd"[..]));
+    /// ```
+    pub fn next_token_bytes(&mut self) -> Option<&[u8]> {
+        let token = self.next_token_span()?;
+        Some(token.bytes(&self.buffer, &self.scratch))
+    }
+
+    /// Reads the next whitespace-delimited token, returning where its bytes are
+    fn next_token_span(&mut self) -> Option<Span> {
+        self.failure = None;
+        let (first, pushed_back) = self.skip_to_token()?;
+        if pushed_back {
+            return Some(self.read_until_slow(Some(first), |c| c.is_ascii_whitespace(), true));
+        }
+        Some(Span::Buffer(self.read_until_range(self.index - 1, |c| c.is_ascii_whitespace())))
+    }
+
+    /// Reads the next whitespace-delimited token from stdin and parses it with `str::parse`
+    ///
+    /// A fallback for types without dedicated support. Returns `None` if parsing fails
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// use std::net::Ipv4Addr;
+    ///
+    /// let mut reader = comp_io::Reader::from_str("127.0.0.1");
+    /// assert_eq!(reader.next_parse::<Ipv4Addr>(), Some(Ipv4Addr::LOCALHOST));
+    /// ```
+    pub fn next_parse<T: FromStr>(&mut self) -> Option<T> {
+        let value = self.next_token_str()?.parse().ok();
+        if value.is_none() {
+            self.failure = Some(ReadErrorKind::Invalid);
+        }
+        value
+    }
+
+    /// Reads the next whitespace-delimited token from stdin as a `Vec<u8>`
+    pub fn next_bytes(&mut self) -> Option<Vec<u8>> {
+        self.next_token_bytes().map(<[u8]>::to_vec)
+    }
+
+    /// Reads the next whitespace-delimited token from stdin as a `Vec<char>`, returning `None` if it is not valid UTF-8
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("abc");
+    /// assert_eq!(reader.next_chars(), Some(vec!['a', 'b', 'c']));
+    /// ```
+    pub fn next_chars(&mut self) -> Option<Vec<char>> {
+        Some(self.next_token_str()?.chars().collect())
+    }
+
+    /// Reads the next whitespace-delimited token from stdin, returning `None` if it isn't exactly `n` bytes long
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("3 abc");
+    /// let n = reader.next_usize().unwrap();
+    /// assert_eq!(reader.next_exact(n), Some(b"abc".to_vec()));
+    /// ```
+    pub fn next_exact(&mut self, n: usize) -> Option<Vec<u8>> {
+        let token = self.next_token_bytes()?;
+        if token.len() != n {
+            self.failure = Some(ReadErrorKind::Invalid);
+            return None;
+        }
+        Some(token.to_vec())
+    }
+
+    /// Consumes bytes up to and including the first one matching `stop`, returning the bytes before it
+    ///
+    /// A `\r` stop byte followed by `\n` consumes both, so CRLF input behaves like LF input
+    fn read_until(&mut self, stop: impl Fn(u8) -> bool) -> &[u8] {
+        let span = if self.pushback.is_empty() {
+            Span::Buffer(self.read_until_range(self.index, stop))
+        } else {
+            self.read_until_slow(None, stop, true)
+        };
+        span.bytes(&self.buffer, &self.scratch)
+    }
+
+    /// Same as `read_until`, copying the bytes into `scratch` one at a time. Used when there are pushed back
+    /// bytes, which aren't in the buffer. `first` was already consumed, and the stop byte is kept unread
+    /// unless `consume_stop` is set
+    fn read_until_slow(&mut self, first: Option<u8>, stop: impl Fn(u8) -> bool, consume_stop: bool) -> Span {
+        self.scratch.clear();
+        self.scratch.extend(first);
+        while let Some(c) = self.peek() {
+            if stop(c) {
+                if consume_stop {
+                    self.next();
+                    self.consume_crlf(Some(c));
+                }
+                break;
+            }
+            self.scratch.push(c);
+            self.next();
+        }
+        Span::Scratch
+    }
+
+    /// Same as `read_until` starting from `start` in the buffer, returning where the bytes are in it
+    fn read_until_range(&mut self, mut start: usize, stop: impl Fn(u8) -> bool) -> Range<usize> {
+        let len = loop {
+            if self.index >= self.len {
+                // the slice continues past the buffer, so keep it while reading more
+                let taken = self.index - start;
+                let more = self.refill(start);
+                start = self.index - taken;
+                if !more {
+                    break self.len - start;
+                }
+            }
+            self.index += 1;
+            if stop(self.buffer[self.index - 1]) {
+                break self.index - 1 - start;
+            }
+        };
+        if self.crlf && self.buffer[..self.len].get(start + len) == Some(&b'\r') {
+            if self.index >= self.len {
+                let taken = self.index - start;
+                self.refill(start);
+                start = self.index - taken;
+            }
+            if self.buffer[..self.len].get(self.index) == Some(&b'\n') {
+                self.index += 1;
+            }
+        }
+        start..start + len
+    }
+
+    /// Same as `next_token_bytes`, returning `None` if the token is not valid UTF-8
+    pub fn next_token_str(&mut self) -> Option<&str> {
+        let token = self.next_token_span()?;
+        // only borrows the buffer and scratch, so the failure can still be recorded
+        match std::str::from_utf8(token.bytes(&self.buffer, &self.scratch)) {
+            Ok(token) => Some(token),
+            Err(_) => {
+                self.failure = Some(ReadErrorKind::Invalid);
+                None
+            }
+        }
+    }
+
+    /// Reads the rest of the current line from stdin, without the trailing `\n` or `\r\n`
+    ///
+    /// Returns `None` at the end of input, or if the line is not valid UTF-8
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("3\nhello world\r\n");
+    /// assert_eq!(reader.next_i32(), Some(3));
+    /// assert_eq!(reader.next_line().unwrap(), "hello world");
+    /// ```
+    pub fn next_line(&mut self) -> Option<String> {
+        self.peek()?;
+        let line = self.read_until(|c| c == b'\n');
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        String::from_utf8(line.to_vec()).ok()
+    }
+
+    /// Reads the lines of the current block, up to the next blank line or the end of input
+    ///
+    /// Blank lines before the block are skipped, and the blank line ending it is consumed.
+    /// Returns `None` if there are no more blocks
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("a b\nc\n\nd\n");
+    /// assert_eq!(reader.read_until_blank_line().unwrap(), vec!["a b", "c"]);
+    /// assert_eq!(reader.read_until_blank_line().unwrap(), vec!["d"]);
+    /// assert_eq!(reader.read_until_blank_line(), None);
+    /// ```
+    pub fn read_until_blank_line(&mut self) -> Option<Vec<String>> {
+        let mut lines = Vec::new();
+        while let Some(line) = self.next_line() {
+            if !line.trim().is_empty() {
+                lines.push(line);
+            } else if !lines.is_empty() {
+                break;
+            }
+        }
+        (!lines.is_empty()).then_some(lines)
+    }
+
+    /// Returns an iterator over the remaining blank-line separated blocks, see `read_until_blank_line`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1\n2\n\n3\n");
+    /// assert_eq!(reader.blocks().count(), 2);
+    /// ```
+    pub fn blocks(&mut self) -> Blocks<'_, R> {
+        Blocks { reader: self }
+    }
+
+    /// Skips the next whitespace-delimited token without allocating or parsing it
+    pub fn skip_token(&mut self) -> Option<()> {
+        self.next_token_bytes().map(|_| ())
+    }
+
+    /// Skips the next `n` whitespace-delimited tokens, returning `None` if the input ends first
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("label 1 2 3\nunused line\n4");
+    /// reader.skip_tokens(4).unwrap();
+    /// reader.skip_line().unwrap();
+    /// assert_eq!(reader.next_i32(), Some(4));
+    /// ```
+    pub fn skip_tokens(&mut self, n: usize) -> Option<()> {
+        for _ in 0..n {
+            self.skip_token()?;
+        }
+        Some(())
+    }
+
+    /// Skips the rest of the current line, including the line ending
+    pub fn skip_line(&mut self) -> Option<()> {
+        self.peek()?;
+        self.read_until(|c| c == b'\n');
+        Some(())
+    }
+
+    /// Consumes all remaining input, returning it as bytes
+    pub fn read_rest_bytes(&mut self) -> Vec<u8> {
+        self.read_until(|_| false).to_vec()
+    }
+
+    /// Consumes all remaining input, returning `None` if it is not valid UTF-8
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1 free-form\ntext");
+    /// assert_eq!(reader.next_i32(), Some(1));
+    /// assert_eq!(reader.read_rest().unwrap(), "free-form\ntext");
+    /// assert_eq!(reader.read_rest().unwrap(), "");
+    /// ```
+    pub fn read_rest(&mut self) -> Option<String> {
+        String::from_utf8(self.read_rest_bytes()).ok()
+    }
+
+    /// Returns the next byte without consuming it
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("-5");
+    /// assert_eq!(reader.peek(), Some(b'-'));
+    /// assert_eq!(reader.next_i32(), Some(-5));
+    /// assert_eq!(reader.peek(), None);
+    /// ```
+    pub fn peek(&mut self) -> Option<u8> {
+        if let Some(&byte) = self.pushback.last() {
+            return Some(byte);
+        }
+        if self.index >= self.len && !self.refill(self.len) {
+            return None;
+        }
+        Some(self.buffer[self.index])
+    }
+
+    /// Puts `byte` back in front of the remaining input, so it is the next byte read
+    ///
+    /// Handing back the byte that was just consumed, such as the delimiter after a number, costs nothing.
+    /// Other bytes are kept apart from the input, so `reset` or `rollback` still see it unchanged
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("12x");
+    /// assert_eq!(reader.next_i32(), Some(12));
+    /// reader.unread(b'x');
+    /// assert_eq!(reader.next_char(), Some('x'));
+    /// ```
+    pub fn unread(&mut self, byte: u8) {
+        if self.pushback.is_empty() && self.index > 0 && self.buffer[self.index - 1] == byte {
+            self.index -= 1;
+        } else {
+            self.push_back([byte]);
+        }
+    }
+
+    /// Returns the next whitespace-delimited token without consuming it
+    ///
+    /// Leading whitespace is skipped, so a number read afterwards starts right at the token
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str(" 42 add");
+    /// if reader.peek_token().unwrap()[0].is_ascii_digit() {
+    ///     assert_eq!(reader.next_i32(), Some(42));
+    /// }
+    /// assert_eq!(reader.peek_token(), Some(&b"add"[..]));
+    /// assert_eq!(reader.next_token().unwrap(), "add");
+    /// ```
+    pub fn peek_token(&mut self) -> Option<&[u8]> {
+        let (first, pushed_back) = self.skip_to_token()?;
+        if pushed_back {
+            // the token doesn't start in the buffer, so it is read out and handed back instead
+            self.read_until_slow(Some(first), |c| c.is_ascii_whitespace(), false);
+            let token = std::mem::take(&mut self.scratch);
+            self.push_back(token.iter().rev().copied());
+            self.scratch = token;
+            return Some(&self.scratch);
+        }
+        self.index -= 1;
+        let mut end = self.index;
+        loop {
+            if end >= self.len {
+                // keep the token in the buffer while reading more
+                let taken = end - self.index;
+                let more = self.refill(self.index);
+                end = self.index + taken;
+                if !more {
+                    break;
+                }
+            }
+            if self.buffer[end].is_ascii_whitespace() {
+                break;
+            }
+            end += 1;
+        }
+        Some(&self.buffer[self.index..end])
+    }
+
+    /// Skips whitespace, returning whether there is another token to read
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("3 1 2\n");
+    /// let mut sum = 0;
+    /// while reader.has_next() {
+    ///     sum += reader.next_i32().unwrap();
+    /// }
+    /// assert_eq!(sum, 6);
+    /// ```
+    pub fn has_next(&mut self) -> bool {
+        match self.skip_whitespace() {
+            Some(c) => {
+                self.unread(c);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns whether nothing but whitespace is left in the input, consuming that whitespace
+    pub fn is_exhausted(&mut self) -> bool {
+        !self.has_next()
+    }
+
+    /// Checks that nothing but whitespace is left in the input, to catch input that was never read
+    ///
+    /// # Panics:
+    ///
+    /// Panics with the position of the first unread token if there is one
+    ///
+    /// # Example:
+    ///
+    /// ```should_panic
+    /// let mut reader = comp_io::Reader::from_str("1 2\n3\n");
+    /// reader.next_pair();
+    /// reader.assert_exhausted();
+    /// ```
+    pub fn assert_exhausted(&mut self) {
+        if !self.is_exhausted() {
+            panic!("expected end of input at {}", self.line_col());
+        }
+    }
+
+    /// After a `\r` delimiter, also consumes the `\n` of the line ending, so CRLF input behaves like LF input
+    fn consume_crlf(&mut self, delimiter: Option<u8>) {
+        if self.crlf && delimiter == Some(b'\r') && self.peek() == Some(b'\n') {
+            self.next();
+        }
+    }
+
+    /// Consumes the next byte if it is whitespace, treating `\r\n` as one delimiter
+    fn skip_delimiter(&mut self) {
+        if let Some(c) = self.peek().filter(u8::is_ascii_whitespace) {
+            self.next();
+            self.consume_crlf(Some(c));
+        }
+    }
+
+    /// Consumes the next `n` bytes, which have to be available already, such as the token from `peek_token`
+    pub(crate) fn skip_bytes(&mut self, n: usize) {
+        let pushed_back = n.min(self.pushback.len());
+        self.pushback.truncate(self.pushback.len() - pushed_back);
+        self.update_fast_len();
+        self.index += n - pushed_back;
+    }
+
+    /// Puts `bytes` in front of the remaining input, the last one first, without changing the buffer
+    fn push_back(&mut self, bytes: impl IntoIterator<Item = u8>) {
+        self.pushback.extend(bytes);
+        self.update_fast_len();
+    }
+
+    /// Takes the next pushed back byte
+    fn pop_pushback(&mut self) -> Option<u8> {
+        let byte = self.pushback.pop();
+        self.update_fast_len();
+        byte
+    }
+
+    /// Drops all pushed back bytes, for when the input is rewound
+    fn clear_pushback(&mut self) {
+        self.pushback.clear();
+        self.update_fast_len();
+    }
+
+    /// Keeps `fast_len` in step with `len` and the pushed back bytes
+    fn update_fast_len(&mut self) {
+        self.fast_len = if self.pushback.is_empty() { self.len } else { 0 };
+    }
+
+    /// Records why a parse failed for `last_error`, and in resync mode consumes the rest of the token it stopped in,
+    /// unless `last` already ended it
+    fn fail(&mut self, kind: ReadErrorKind, last: Option<u8>) {
+        self.failure = Some(kind);
+        if self.resync && last.is_some_and(|c| !c.is_ascii_whitespace()) {
+            self.read_until(|c| c.is_ascii_whitespace());
+        }
+    }
+
+    /// Consumes the first byte of a value, skipping whitespace before it in lenient mode
+    #[inline]
+    fn value_start(&mut self) -> Option<u8> {
+        self.failure = None;
+        match self.whitespace {
+            Whitespace::Single => self.next(),
+            Whitespace::Lenient => self.skip_whitespace(),
+        }
+    }
+
+    /// Consumes whitespace, returning the first byte after it
+    fn skip_whitespace(&mut self) -> Option<u8> {
+        self.find(|c| !c.is_ascii_whitespace())
+    }
+
+    /// Same as `skip_whitespace`, also returning whether the byte was pushed back rather than taken from the buffer
+    fn skip_to_token(&mut self) -> Option<(u8, bool)> {
+        loop {
+            let pushed_back = !self.pushback.is_empty();
+            let c = self.next()?;
+            if !c.is_ascii_whitespace() {
+                return Some((c, pushed_back));
+            }
+        }
+    }
+
+    /// Reads the next pair of i32s from stdin
+    pub fn next_pair(&mut self) -> Option<(i32, i32)> {
+        Some((self.read_i32()?.0, self.read_i32()?.0))
+    }
+
+    /// Reads the next f64 from stdin
+    ///
+    /// Accepts an optional exponent suffix such as `1.5e-9` or `3E+7`, as well as `inf`, `infinity` and `nan`.
+    /// The result is correctly rounded, matching `str::parse::<f64>` bit-for-bit
+    pub fn next_f64(&mut self) -> Option<f64> {
+        self.scratch.clear();
+        let first = self.value_start()?;
+        let neg = first == b'-';
+        let mut val = if neg || first == b'+' { self.next() } else { Some(first) };
+        if let Some(b'i' | b'I' | b'n' | b'N') = val {
+            return self.read_special_float(val, neg);
+        }
+
+        // Collect up to 19 significant digits into an integer mantissa, so `value = mantissa * 10^exp`
+        let (mut mantissa, mut exp, mut truncated, mut any_digit) = (0u64, 0i32, false, false);
+        while let Some(d @ b'0'..=b'9') = val {
+            self.scratch.push(d);
+            if mantissa < 1_000_000_000_000_000_000 {
+                mantissa = mantissa * 10 + (d - b'0') as u64;
+            } else {
+                exp += 1;
+                truncated |= d != b'0';
+            }
+            any_digit = true;
+            val = self.next();
+        }
+        if val == Some(self.decimal_separator) {
+            self.scratch.push(b'.');
+            val = self.next();
+            while let Some(d @ b'0'..=b'9') = val {
+                self.scratch.push(d);
+                if mantissa < 1_000_000_000_000_000_000 {
+                    mantissa = mantissa * 10 + (d - b'0') as u64;
+                    exp -= 1;
+                } else {
+                    truncated |= d != b'0';
+                }
+                any_digit = true;
+                val = self.next();
+            }
+        }
+        if !any_digit {
+            self.fail(ReadErrorKind::Invalid, val);
+            return None;
+        }
+        if let Some(e @ (b'e' | b'E')) = val {
+            self.scratch.push(e);
+            val = self.next();
+            let neg_exp = val == Some(b'-');
+            if neg_exp || val == Some(b'+') {
+                self.scratch.push(val.unwrap());
+                val = self.next();
+            }
+            let (mut e, mut any_exp_digit) = (0i32, false);
+            while let Some(d @ b'0'..=b'9') = val {
+                self.scratch.push(d);
+                e = e.saturating_mul(10).saturating_add((d - b'0') as i32);
+                any_exp_digit = true;
+                val = self.next();
+            }
+            if !any_exp_digit {
+                self.fail(ReadErrorKind::Invalid, val);
+                return None;
+            }
+            exp = exp.saturating_add(if neg_exp { -e } else { e });
+        }
+        self.consume_crlf(val);
+
+        let value = if !truncated && mantissa <= 1 << 53 && (-22..=22).contains(&exp) {
+            // Both operands are exact, so a single multiplication or division is correctly rounded
+            if exp < 0 {
+                mantissa as f64 / POW10[-exp as usize]
+            } else {
+                mantissa as f64 * POW10[exp as usize]
+            }
+        } else {
+            std::str::from_utf8(&self.scratch).ok()?.parse().ok()?
+        };
+        Some(if neg { -value } else { value })
+    }
+
+    /// Reads the next `hh:mm:ss` time from stdin as a total number of seconds
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("12:34:56");
+    /// assert_eq!(reader.next_hhmmss(), Some(12 * 3600 + 34 * 60 + 56));
+    /// ```
+    pub fn next_hhmmss(&mut self) -> Option<i64> {
+        let hours = self.read_i64_before(b':')?;
+        let minutes = self.read_i64_before(b':')?;
+        Some((hours * 60 + minutes) * 60 + self.read_i64()?.0)
+    }
+
+    /// Reads the next `hh:mm` time from stdin as a total number of minutes
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("09:05");
+    /// assert_eq!(reader.next_hhmm(), Some(9 * 60 + 5));
+    /// ```
+    pub fn next_hhmm(&mut self) -> Option<i64> {
+        let hours = self.read_i64_before(b':')?;
+        Some(hours * 60 + self.read_i64()?.0)
+    }
+
+    /// Reads an i64 that must be terminated by `delimiter`
+    fn read_i64_before(&mut self, delimiter: u8) -> Option<i64> {
+        let (value, found) = self.read_i64()?;
+        if found != delimiter {
+            // a digit means the input ended right after the number, which `last_error` reports as `Eof`
+            if !found.is_ascii_digit() {
+                self.fail(ReadErrorKind::UnexpectedByte(found), Some(found));
+            }
+            return None;
+        }
+        Some(value)
+    }
+
+    /// Reads the digits of the next number from stdin as values `0..=9`, most significant first
+    ///
+    /// Works for numbers of any length. A leading sign is dropped, same as `next_u32`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("-1230");
+    /// assert_eq!(reader.next_digits(), Some(vec![1, 2, 3, 0]));
+    /// ```
+    pub fn next_digits(&mut self) -> Option<Vec<u8>> {
+        let mut val = match self.value_start()? {
+            b'-' | b'+' => self.next(),
+            v => Some(v),
+        };
+        let mut digits = Vec::new();
+        while let Some(d @ b'0'..=b'9') = val {
+            digits.push(d - b'0');
+            val = self.next();
+        }
+        self.consume_crlf(val);
+        if digits.is_empty() {
+            self.fail_without_digits(val);
+            return None;
+        }
+        Some(digits)
+    }
+
+    /// Reads the next hexadecimal u64 from stdin, with or without a `0x` prefix
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("0x1F ff");
+    /// assert_eq!(reader.next_hex_u64(), Some(31));
+    /// assert_eq!(reader.next_hex_u64(), Some(255));
+    /// ```
+    pub fn next_hex_u64(&mut self) -> Option<u64> {
+        let val = self.value_start();
+        if val != Some(b'0') {
+            return self.read_radix_digits(val, 16, false);
+        }
+        match self.next() {
+            Some(b'x' | b'X') => {
+                let val = self.next();
+                self.read_radix_digits(val, 16, false)
+            }
+            val => self.read_radix_digits(val, 16, true), // the leading zero was a digit
+        }
+    }
+
+    /// Reads the next u64 written in the given base from stdin. Digits past 9 are letters of either case
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `base` is not in the range `2..=36`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1011 zz");
+    /// assert_eq!(reader.next_radix(2), Some(11));
+    /// assert_eq!(reader.next_radix(36), Some(1295));
+    /// ```
+    pub fn next_radix(&mut self, base: u32) -> Option<u64> {
+        assert!((2..=36).contains(&base), "base must be in 2..=36, got {base}");
+        let val = self.value_start();
+        self.read_radix_digits(val, base, false)
+    }
+
+    /// Reads the next binary string from stdin as a u64, with the first character as the most significant bit
+    pub fn next_binary_u64(&mut self) -> Option<u64> {
+        let val = self.value_start();
+        self.read_radix_digits(val, 2, false)
+    }
+
+    /// Reads the next binary string from stdin packed into 64-bit words
+    ///
+    /// Character `i` of the string is stored in bit `i % 64` of word `i / 64`, so indices match the string
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1101");
+    /// assert_eq!(reader.next_bitset(), Some(vec![0b1011]));
+    /// ```
+    pub fn next_bitset(&mut self) -> Option<Vec<u64>> {
+        let (mut words, mut i) = (Vec::new(), 0);
+        let mut val = self.value_start();
+        while let Some(b @ (b'0' | b'1')) = val {
+            if i % 64 == 0 {
+                words.push(0);
+            }
+            words[i / 64] |= ((b - b'0') as u64) << (i % 64);
+            i += 1;
+            val = self.next();
+        }
+        self.consume_crlf(val);
+        if i == 0 {
+            self.fail_without_digits(val);
+            return None;
+        }
+        Some(words)
+    }
+
+    /// Accumulates digits in the given radix starting at `val`, consuming the byte after them
+    ///
+    /// `any_digit` tells whether a digit was already consumed by the caller. Returns `None` if no digits were found
+    /// or the value doesn't fit in a u64, in which case the rest of the digits are still consumed
+    fn read_radix_digits(&mut self, mut val: Option<u8>, radix: u32, mut any_digit: bool) -> Option<u64> {
+        let mut r = Some(0u64);
+        while let Some(d) = val.and_then(|v| (v as char).to_digit(radix)) {
+            r = r.and_then(|r| r.checked_mul(radix as u64)?.checked_add(d as u64));
+            any_digit = true;
+            val = self.next();
+        }
+        self.consume_crlf(val);
+        if !any_digit {
+            self.fail_without_digits(val);
+            return None;
+        }
+        if r.is_none() {
+            self.fail(ReadErrorKind::Overflow, val);
+        }
+        r
+    }
+
+    /// Records a value that ended at `val` before any digit, unless the input ended
+    fn fail_without_digits(&mut self, val: Option<u8>) {
+        if let Some(b) = val {
+            self.fail(ReadErrorKind::UnexpectedByte(b), val);
+        }
+    }
+
+    /// Reads the next fraction of the form `p/q` from stdin as `(p, q)`. A bare integer `p` is read as `(p, 1)`
+    ///
+    /// The fraction is returned as written, without reducing it
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("3/4 -6/8 5");
+    /// assert_eq!(reader.next_fraction(), Some((3, 4)));
+    /// assert_eq!(reader.next_fraction(), Some((-6, 8)));
+    /// assert_eq!(reader.next_fraction(), Some((5, 1)));
+    /// ```
+    pub fn next_fraction(&mut self) -> Option<(i64, i64)> {
+        let (p, delimiter) = self.read_i64()?;
+        if delimiter != b'/' {
+            return Some((p, 1));
+        }
+        Some((p, self.read_i64()?.0))
+    }
+
+    /// Reads the next decimal number from stdin as an integer scaled by `10^SCALE`, avoiding floating point
+    ///
+    /// Missing fractional digits are treated as zeros, and digits past `SCALE` are truncated. Returns `None` if the
+    /// scaled value doesn't fit in an i64. `SCALE` can be at most 18, since `10^19` doesn't fit either, which is
+    /// checked at compile time
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("12.345 -0.5 7");
+    /// assert_eq!(reader.next_fixed::<3>(), Some(12345));
+    /// assert_eq!(reader.next_fixed::<3>(), Some(-500));
+    /// assert_eq!(reader.next_fixed::<3>(), Some(7000));
+    /// ```
+    pub fn next_fixed<const SCALE: u32>(&mut self) -> Option<i64> {
+        const { assert!(SCALE <= 18, "next_fixed supports a SCALE of at most 18") };
+        let first = self.value_start()?;
+        let neg = first == b'-';
+        let mut val = if neg || first == b'+' { self.next() } else { Some(first) };
+
+        // accumulated as a negative number for the same reason as `read_signed`, and like `checked_next_i64`
+        // the digits after an overflow are still consumed
+        let (mut r, mut any_digit) = (Some(0i64), false);
+        while let Some(d @ b'0'..=b'9') = val {
+            r = r.and_then(|r| r.checked_mul(10)?.checked_sub((d - b'0') as i64));
+            any_digit = true;
+            val = self.next();
+        }
+        let mut scale = SCALE;
+        if val == Some(self.decimal_separator) {
+            val = self.next();
+            while let Some(d @ b'0'..=b'9') = val {
+                if scale > 0 {
+                    r = r.and_then(|r| r.checked_mul(10)?.checked_sub((d - b'0') as i64));
+                    scale -= 1;
+                }
+                any_digit = true;
+                val = self.next();
+            }
+        }
+        self.consume_crlf(val);
+        if !any_digit {
+            self.fail(ReadErrorKind::Invalid, val);
+            return None;
+        }
+        let r = r.and_then(|r| r.checked_mul(10i64.pow(scale)));
+        let r = if neg { r } else { r.and_then(i64::checked_neg) };
+        if r.is_none() {
+            self.fail(ReadErrorKind::Overflow, val);
+        }
+        r
+    }
+
+    /// Reads the rest of an `inf`, `infinity` or `nan` token (case-insensitive), starting at `val`
+    fn read_special_float(&mut self, mut val: Option<u8>, neg: bool) -> Option<f64> {
+        while let Some(c) = val.filter(u8::is_ascii_alphabetic) {
+            self.scratch.push(c.to_ascii_lowercase());
+            val = self.next();
+        }
+        self.consume_crlf(val);
+        let value = match &self.scratch[..] {
+            b"inf" | b"infinity" => f64::INFINITY,
+            b"nan" => f64::NAN,
+            _ => {
+                self.fail(ReadErrorKind::Invalid, val);
+                return None;
+            }
+        };
+        Some(if neg { -value } else { value })
+    }
+}
+
+/// Where the bytes of a token or line were read to
+enum Span {
+    /// Straight out of the buffer
+    Buffer(Range<usize>),
+    /// Copied into `scratch`, since they started with pushed back bytes
+    Scratch,
+}
+
+impl Span {
+    /// Takes the fields instead of the reader, so the reader's other fields can still be changed while it is borrowed
+    fn bytes<'a>(self, buffer: &'a [u8], scratch: &'a [u8]) -> &'a [u8] {
+        match self {
+            Span::Buffer(range) => &buffer[range],
+            Span::Scratch => scratch,
+        }
+    }
+}
+
+/// Powers of ten that are exactly representable as an f64
+const POW10: [f64; 23] = [
+    1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10, 1e11, 1e12, 1e13, 1e14, 1e15, 1e16,
+    1e17, 1e18, 1e19, 1e20, 1e21, 1e22,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hands out at most 3 bytes per read, like a slow pipe
+    struct Trickle(io::Cursor<Vec<u8>>);
+
+    impl Trickle {
+        fn new(input: &str) -> Self {
+            Trickle(io::Cursor::new(input.as_bytes().to_vec()))
+        }
+    }
+
+    impl Read for Trickle {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(3);
+            self.0.read(&mut buf[..n])
+        }
+    }
+
+    #[test]
+    fn test_reader_from_str() {
+        let reader = Reader::from_str("-4.1");
+
+        assert_eq!(reader.buffer, vec![45, 52, 46, 49]);
+        assert_eq!(reader.index, 0);
+        assert_eq!(reader.len, 4);
+    }
+
+    #[test]
+    fn test_reader_from() {
+        let mut readers = [
+            Reader::from("1 2"),
+            Reader::from(String::from("1 2")),
+            Reader::from(&b"1 2"[..]),
+            Reader::from(b"1 2".to_vec()),
+        ];
+        for reader in &mut readers {
+            assert_eq!(reader.next_pair(), Some((1, 2)));
+        }
+    }
+
+    #[test]
+    fn test_large_in_memory_input() {
+        let input = "7 ".repeat(300_000);
+        let mut reader = Reader::from_str(&input);
+        assert_eq!(reader.iter::<u8>().count(), 300_000);
+        assert_eq!(reader.next(), None);
+    }
+
+    #[test]
+    fn test_refill_across_buffer_boundary() {
+        // tokens, numbers and lines straddle the 400_000 byte refill boundary
+        let words: Vec<String> = (0..120_000).map(|i| format!("w{i}")).collect();
+        let input = format!("{}\r\n123456789\r\nlast line\r\n", words.join(" "));
+        let mut reader = Reader::from_reader(io::Cursor::new(input.into_bytes()));
+        for word in &words {
+            assert_eq!(reader.next_token_str(), Some(word.as_str()));
+        }
+        assert_eq!(reader.next_i64(), Some(123_456_789));
+        assert_eq!(reader.line_col().line, 3);
+        assert_eq!(reader.next_line().unwrap(), "last line");
+        assert_eq!(reader.next_line(), None);
+    }
+
+    #[test]
+    fn test_peek_token_across_refill() {
+        // an interactive reader refills once per 3 byte read, so the token is split over several refills
+        let mut reader = Reader::from_reader(Trickle::new("  query 7")).interactive();
+        assert_eq!(reader.peek_token(), Some(&b"query"[..]));
+        assert_eq!(reader.peek(), Some(b'q'));
+        assert_eq!(reader.next_token().unwrap(), "query");
+        assert_eq!(reader.peek_token(), Some(&b"7"[..]));
+        assert_eq!(reader.next_i32(), Some(7));
+        assert_eq!(reader.peek_token(), None);
+    }
+
+    #[test]
+    fn test_unread() {
+        let mut reader = Reader::from_str("5 a");
+        assert_eq!(reader.next_i32(), Some(5));
+        reader.unread(b' ');
+        assert_eq!(reader.next_char(), Some(' '));
+        assert_eq!(reader.next_char(), Some('a'));
+        reader.unread(b'b');
+        reader.unread(b'c');
+        assert_eq!(reader.next_token().unwrap(), "cb");
+
+        let mut reader = Reader::from_str("");
+        reader.unread(b'7');
+        assert_eq!(reader.next_i32(), Some(7));
+
+        // pushed back bytes never change the input itself
+        let mut reader = Reader::from_str("12x 3\nz");
+        assert_eq!(reader.next_i32(), Some(12));
+        reader.unread(b'y');
+        reader.unread(b'a');
+        let copy = reader.clone();
+        assert_eq!(reader.peek_token(), Some(&b"ay"[..]));
+        assert!(reader.has_next());
+        assert_eq!(reader.next_token().unwrap(), "ay");
+        assert_eq!(reader.next_i32(), Some(3));
+        reader.unread(b'\n');
+        reader.unread(b'q');
+        assert_eq!(reader.next_line().unwrap(), "q");
+        assert_eq!(reader.next_line().unwrap(), "z");
+        reader.reset();
+        assert_eq!(reader.read_rest().unwrap(), "12x 3\nz");
+        assert_eq!(copy.clone().read_rest_bytes(), b"ay 3\nz");
+
+        // a single pushed back byte, at the start and in the middle of the buffer
+        let mut reader = Reader::from_str("bc");
+        reader.unread(b'a');
+        assert_eq!(reader.peek_token(), Some(&b"abc"[..]));
+        assert_eq!(reader.next_token().unwrap(), "abc");
+        let mut reader = Reader::from_str("ab cd");
+        assert_eq!(reader.next_char(), Some('a'));
+        reader.unread(b'q');
+        assert_eq!(reader.peek_token(), Some(&b"qb"[..]));
+        assert_eq!(reader.next_token().unwrap(), "qb");
+        reader.unread(b'x');
+        assert_eq!(reader.next_token().unwrap(), "xcd");
+    }
+
+    #[test]
+    fn test_mark_rollback() {
+        let mut reader = Reader::from_str("1.5 x\n2");
+        let start = reader.mark();
+        assert_eq!(reader.next_f64(), Some(1.5));
+        let after = reader.mark();
+        assert_eq!(reader.next_f64(), None);
+        reader.rollback(after);
+        assert_eq!(reader.next_token().unwrap(), "x");
+        assert_eq!(reader.line_col().line, 2);
+        reader.rollback(start);
+        assert_eq!(reader.line_col(), Position { line: 1, column: 1 });
+        assert_eq!(reader.next_token().unwrap(), "1.5");
+    }
+
+    #[test]
+    fn test_has_next() {
+        let mut reader = Reader::from_reader(Trickle::new("7\r\n  \n -2 \n\n"));
+        let mut values = Vec::new();
+        while reader.has_next() {
+            values.push(reader.next_i32().unwrap());
+        }
+        assert_eq!(values, vec![7, -2]);
+        assert!(!reader.has_next());
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut reader = Reader::from_str("ab\ncd");
+        reader.skip_line();
+        assert_eq!(reader.byte_pos(), 3);
+        reader.seek(1);
+        assert_eq!(reader.next_token().unwrap(), "b");
+        reader.seek(5);
+        assert_eq!(reader.next_token(), None);
+    }
+
+    #[test]
+    fn test_tokens() {
+        let mut reader = Reader::from_str("3\n ab  c\r\nd \n");
+        assert_eq!(reader.next_usize(), Some(3));
+        assert_eq!(reader.remaining(), b" ab  c\r\nd \n");
+        let tokens: Vec<&[u8]> = reader.tokens().collect();
+        assert_eq!(tokens, [&b"ab"[..], b"c", b"d"]);
+        assert_eq!(reader.tokens().count(), 3);
+        reader.skip_tokens(3);
+        assert_eq!(reader.tokens().next(), None);
+    }
+
+    #[test]
+    fn test_exhausted() {
+        let mut reader = Reader::from_str("4 x \r\n\n");
+        assert!(!reader.is_exhausted());
+        reader.next_i32();
+        let unread = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| reader.assert_exhausted()));
+        assert!(unread.is_err());
+        reader.skip_token();
+        assert!(reader.is_exhausted());
+        reader.assert_exhausted();
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut reader = Reader::from_str("3 1 2 3");
+        let n = reader.next_usize().unwrap();
+        let mut copy = reader.clone();
+        assert_eq!(reader.next_vec::<i32>(n), Some(vec![1, 2, 3]));
+        assert_eq!(copy.next_vec::<u8>(n), Some(vec![1, 2, 3]));
+        assert_eq!(copy.line_col(), reader.line_col());
+    }
+
+    #[test]
+    fn test_resync() {
+        let mut reader = Reader::from_str("ab 1 x2 2.5e 4.0 ?\r\n5").with_resync();
+        assert_eq!(reader.next_i32(), None);
+        assert_eq!(reader.next_i32(), Some(1));
+        assert_eq!(reader.checked_next_i64().map_err(|e| e.kind), Err(ReadErrorKind::UnexpectedByte(b'x')));
+        assert_eq!(reader.next_f64(), None);
+        assert_eq!(reader.next_f64(), Some(4.0));
+        assert_eq!(reader.next_u64(), None);
+        assert_eq!(reader.next_u64(), Some(5));
+    }
+
+    #[test]
+    fn test_chain() {
+        let first = io::Cursor::new("3 4\n");
+        let mut reader = Reader::from_str("2\n").chain(first).chain(&b"5 6\n"[..]);
+        assert_eq!(reader.next_usize(), Some(2));
+        assert_eq!(reader.next_vec::<i32>(4), Some(vec![3, 4, 5, 6]));
+        assert_eq!(reader.next(), None);
+    }
+
+    #[test]
+    fn test_buf_read() {
+        let input = io::Cursor::new("1\nraw line\n7 8\nrest");
+        let mut reader = Reader::from_reader(input);
+        assert_eq!(reader.next_i32(), Some(1));
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line, "raw line\n");
+        assert_eq!(reader.next_pair(), Some((7, 8)));
+        assert_eq!(reader.line_col(), Position { line: 4, column: 1 });
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).unwrap();
+        assert_eq!(rest, "rest");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_from_child() {
+        let mut child = std::process::Command::new("echo")
+            .arg("12 ab")
+            .stdout(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut reader = Reader::from_child(&mut child).unwrap();
+        assert_eq!(reader.next_i32(), Some(12));
+        assert_eq!(reader.next_token(), Some("ab".to_string()));
+        assert!(Reader::from_child(&mut child).is_none());
+        child.wait().unwrap();
+    }
+
+    #[test]
+    fn test_short_reads() {
+        let mut reader = Reader::from_reader(Trickle::new("4\n100 -20 300 -4000\nend\n"));
+        let n = reader.next_usize().unwrap();
+        assert_eq!(reader.next_vec::<i32>(n), Some(vec![100, -20, 300, -4000]));
+        assert_eq!(reader.next_token(), Some("end".to_string()));
+        assert_eq!(reader.next_token(), None);
+    }
+
+    #[test]
+    fn test_interactive() {
+        /// Hands out one line per read, and fails if asked for input it hasn't been sent yet
+        struct Judge(Vec<&'static [u8]>);
+
+        impl Read for Judge {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                let line = self.0.pop().expect("read past the available input");
+                buf[..line.len()].copy_from_slice(line);
+                Ok(line.len())
+            }
+        }
+
+        let mut reader = Reader::from_reader(Judge(vec![b"7\n", b"3 4\n"])).interactive();
+        assert_eq!(reader.next_pair(), Some((3, 4)));
+        assert_eq!(reader.next_i32(), Some(7));
+    }
+
+    #[test]
+    fn test_next_f64() {
+        let mut reader = Reader::from_str("-4 45 -754.3 32. 45");
+        assert_eq!(reader.next_f64().unwrap(), -4.);
+        assert_eq!(reader.next_f64().unwrap(), 45.);
+        assert_eq!(reader.next_f64().unwrap(), -754.3);
+        assert_eq!(reader.next_f64().unwrap(), 32.);
+        assert_eq!(reader.next_f64().unwrap(), 45.);
+    }
+
+    #[test]
+    fn test_next_char() {
+        let mut reader = Reader::from_str("ab cd");
+        assert_eq!(reader.next_char().unwrap(), 'a');
+        assert_eq!(reader.next_char().unwrap(), 'b');
+        assert_eq!(reader.next_char().unwrap(), ' ');
+        assert_eq!(reader.next_char().unwrap(), 'c');
+        assert_eq!(reader.next_char().unwrap(), 'd');
+    }
+
+    #[test]
+    fn test_next_pair() {
+        let mut reader = Reader::from_str("23 32\n12 -34 57 97\n-12 3");
+        assert_eq!(reader.next_pair().unwrap(), (23, 32));
+        assert_eq!(reader.next_pair().unwrap(), (12, -34));
+        assert_eq!(reader.next_pair().unwrap(), (57, 97));
+        assert_eq!(reader.next_pair().unwrap(), (-12, 3));
+    }
+
+    #[test]
     fn test_next_usize() {
         let mut reader = Reader::from_str("23 32\n12\n34");
         assert_eq!(reader.next_usize().unwrap(), 23);
@@ -222,9 +2355,451 @@ mod tests {
         assert_eq!(reader.next_usize().unwrap(), 34);
     }
 
+    #[test]
+    fn test_next_usize_large() {
+        let mut reader = Reader::from_str("2147483648 4294967296 1000000000000000000");
+        assert_eq!(reader.next_usize().unwrap(), 2_147_483_648);
+        assert_eq!(reader.next_usize().unwrap(), 4_294_967_296);
+        assert_eq!(reader.next_usize().unwrap(), 1_000_000_000_000_000_000);
+    }
+
+    #[test]
+    fn test_i32_limits() {
+        let mut reader = Reader::from_str("-2147483648 2147483647 -2147483647 0 -0");
+        assert_eq!(reader.next_i32().unwrap(), i32::MIN);
+        assert_eq!(reader.next_i32().unwrap(), i32::MAX);
+        assert_eq!(reader.next_i32().unwrap(), -i32::MAX);
+        assert_eq!(reader.next_i32().unwrap(), 0);
+        assert_eq!(reader.next_i32().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_next_i64() {
+        let mut reader = Reader::from_str("1000000000000000000 -9000000000000000000\n+42");
+        assert_eq!(reader.next_i64().unwrap(), 1_000_000_000_000_000_000);
+        assert_eq!(reader.next_i64().unwrap(), -9_000_000_000_000_000_000);
+        assert_eq!(reader.next_i64().unwrap(), 42);
+
+        let mut reader = Reader::from_str("-9223372036854775808 9223372036854775807");
+        assert_eq!(reader.next_i64().unwrap(), i64::MIN);
+        assert_eq!(reader.next_i64().unwrap(), i64::MAX);
+    }
+
+    #[test]
+    fn test_next_u64() {
+        let mut reader = Reader::from_str("18446744073709551615 4294967296\n7");
+        assert_eq!(reader.next_u64().unwrap(), u64::MAX);
+        assert_eq!(reader.next_u64().unwrap(), 4_294_967_296);
+        assert_eq!(reader.next_u64().unwrap(), 7);
+    }
+
+    #[test]
+    fn test_next_u32() {
+        let mut reader = Reader::from_str("3000000000 4294967295 -5");
+        assert_eq!(reader.next_t::<u32>(), Some(3_000_000_000));
+        assert_eq!(reader.next_u32(), Some(u32::MAX));
+        assert_eq!(reader.next_u32(), Some(5));
+    }
+
+    #[test]
+    fn test_next_i128() {
+        let mut reader = Reader::from_str(
+            "170141183460469231731687303715884105727 -170141183460469231731687303715884105728\n-1",
+        );
+        assert_eq!(reader.next_i128().unwrap(), i128::MAX);
+        assert_eq!(reader.next_i128().unwrap(), i128::MIN);
+        assert_eq!(reader.next_i128().unwrap(), -1);
+    }
+
+    #[test]
+    fn test_next_u128() {
+        let mut reader = Reader::from_str("340282366920938463463374607431768211455 1000000000000000000000000000000");
+        assert_eq!(reader.next_u128().unwrap(), u128::MAX);
+        assert_eq!(reader.next_u128().unwrap(), 10u128.pow(30));
+    }
+
+    #[test]
+    fn test_checked_next() {
+        let mut reader = Reader::from_str("2147483647 2147483648 -2147483648 -2147483649 7");
+        assert_eq!(reader.checked_next_i32(), Ok(i32::MAX));
+        assert_eq!(reader.checked_next_i32().map_err(|e| e.kind), Err(ReadErrorKind::Overflow));
+        assert_eq!(reader.checked_next_i32(), Ok(i32::MIN));
+        assert_eq!(reader.checked_next_i32().map_err(|e| e.kind), Err(ReadErrorKind::Overflow));
+        assert_eq!(reader.checked_next_i32(), Ok(7));
+        assert_eq!(reader.checked_next_i32().map_err(|e| e.kind), Err(ReadErrorKind::Eof));
+
+        let mut reader = Reader::from_str("18446744073709551615 18446744073709551616 -1 -0 x");
+        assert_eq!(reader.checked_next_u64(), Ok(u64::MAX));
+        assert_eq!(reader.checked_next_u64().map_err(|e| e.kind), Err(ReadErrorKind::Overflow));
+        assert_eq!(reader.checked_next_u64().map_err(|e| e.kind), Err(ReadErrorKind::Overflow));
+        assert_eq!(reader.checked_next_u64(), Ok(0));
+        assert_eq!(reader.checked_next_u64().map_err(|e| e.kind), Err(ReadErrorKind::UnexpectedByte(b'x')));
+    }
+
+    #[test]
+    fn test_try_next() {
+        let mut reader = Reader::from_str("12\nab 3.5\n");
+        assert_eq!(reader.try_next_usize(), Ok(12));
+        let error = reader.try_next_u64().unwrap_err();
+        assert_eq!(error.kind, ReadErrorKind::UnexpectedByte(b'a'));
+        assert_eq!(error.position, Position { line: 2, column: 2 });
+        assert_eq!(reader.try_next_token().unwrap(), "b");
+        assert_eq!(reader.try_next_parse::<u8>().unwrap_err().kind, ReadErrorKind::Invalid);
+        assert_eq!(reader.try_next_token().unwrap_err().kind, ReadErrorKind::Eof);
+    }
+
+    #[test]
+    fn test_last_error() {
+        let mut reader = Reader::from_str("1 2 3");
+        assert_eq!(reader.next_vec::<i32>(4), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Eof));
+
+        let mut reader = Reader::from_str("1 - 3");
+        assert_eq!(reader.next_vec::<f64>(3), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Invalid));
+        assert_eq!(reader.next_f64(), Some(3.0));
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Eof));
+
+        let mut reader = Reader::from_str("256 5 99999999999 y maybe yes 1e");
+        assert_eq!(reader.next_u8(), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Overflow));
+        assert_eq!(reader.next_u8(), Some(5));
+        assert!(reader.checked_next_i32().is_err());
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Overflow));
+        assert_eq!(reader.next_token().unwrap(), "y");
+        assert_eq!(reader.last_error(), None);
+        assert_eq!(reader.next_bool(), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Invalid));
+        assert_eq!(reader.next_bool(), Some(true));
+        assert_eq!(reader.last_error(), None);
+        assert_eq!(reader.next_f64(), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Invalid));
+
+        let mut reader = Reader::from(&b"\xff x 7 ? y 0"[..]);
+        assert!(reader.try_next_token().is_err());
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Invalid));
+        assert!(reader.try_next_parse::<i32>().is_err());
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Invalid));
+        assert_eq!(reader.next_option_token::<i32>("?"), Some(Some(7)));
+        assert_eq!(reader.next_option_token::<i32>("?"), Some(None));
+        assert_eq!(reader.next_option_token::<i32>("?"), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Invalid));
+        assert_eq!(reader.next_t::<Usize1>(), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::OutOfRange));
+    }
+
+    #[test]
+    fn test_expect_read() {
+        let mut reader = Reader::from_str("3 7.5 word\n1 2 oops 4");
+        assert_eq!(reader.usize(), 3);
+        assert_eq!(reader.f64(), 7.5);
+        assert_eq!(reader.token(), "word");
+        assert_eq!((reader.i32(), reader.u64()), (1, 2));
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| reader.i64())).unwrap_err();
+        assert_eq!(
+            panic.downcast_ref::<String>().unwrap(),
+            "couldn't read i64: unexpected byte 'o' at line 2, col 6, near \"3 7.5 word\\n1 2 oops 4\""
+        );
+    }
+
+    #[test]
+    fn test_small_ints() {
+        let mut reader = Reader::from_str("255 256 -128 128 65535 -32768 32768");
+        assert_eq!(reader.next_u8(), Some(255));
+        assert_eq!(reader.next_u8(), None);
+        assert_eq!(reader.next_i8(), Some(-128));
+        assert_eq!(reader.next_i8(), None);
+        assert_eq!(reader.next_u16(), Some(65535));
+        assert_eq!(reader.next_i16(), Some(-32768));
+        assert_eq!(reader.next_i16(), None);
+    }
+
+    #[test]
+    fn test_next_f64_special() {
+        let mut reader = Reader::from_str("inf -inf +Infinity nan -NaN info");
+        assert_eq!(reader.next_f64().unwrap(), f64::INFINITY);
+        assert_eq!(reader.next_f64().unwrap(), f64::NEG_INFINITY);
+        assert_eq!(reader.next_f64().unwrap(), f64::INFINITY);
+        assert!(reader.next_f64().unwrap().is_nan());
+        assert!(reader.next_f64().unwrap().is_sign_negative());
+        assert_eq!(reader.next_f64(), None);
+    }
+
+    #[test]
+    fn test_decimal_separator() {
+        let mut reader = Reader::from_str("3,25 -0,5 2 1,5e3").with_decimal_separator(b',');
+        assert_eq!(reader.next_f64().unwrap(), 3.25);
+        assert_eq!(reader.next_f64().unwrap(), -0.5);
+        assert_eq!(reader.next_f64().unwrap(), 2.);
+        assert_eq!(reader.next_f64().unwrap(), 1500.);
+    }
+
+    #[test]
+    fn test_next_fixed() {
+        let mut reader = Reader::from_str("12.345 12.3 -0.001 5 3.14159 +.5 x");
+        assert_eq!(reader.next_fixed::<3>(), Some(12345));
+        assert_eq!(reader.next_fixed::<3>(), Some(12300));
+        assert_eq!(reader.next_fixed::<3>(), Some(-1));
+        assert_eq!(reader.next_fixed::<3>(), Some(5000));
+        assert_eq!(reader.next_fixed::<2>(), Some(314));
+        assert_eq!(reader.next_fixed::<0>(), Some(0));
+        assert_eq!(reader.next_fixed::<3>(), None);
+
+        let mut reader = Reader::from_str("9.223372036854775807 -9.223372036854775808 9.3 10000000000000000000 1");
+        assert_eq!(reader.next_fixed::<18>(), Some(i64::MAX));
+        assert_eq!(reader.next_fixed::<18>(), Some(i64::MIN));
+        assert_eq!(reader.next_fixed::<18>(), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Overflow));
+        assert_eq!(reader.next_fixed::<0>(), None);
+        assert_eq!(reader.next_fixed::<0>(), Some(1));
+    }
+
+    #[test]
+    fn test_next_fraction() {
+        let mut reader = Reader::from_str("1/2\n-3/7 10 4/-5 9/");
+        assert_eq!(reader.next_fraction(), Some((1, 2)));
+        assert_eq!(reader.next_fraction(), Some((-3, 7)));
+        assert_eq!(reader.next_fraction(), Some((10, 1)));
+        assert_eq!(reader.next_fraction(), Some((4, -5)));
+        assert_eq!(reader.next_fraction(), None);
+    }
+
+    #[test]
+    fn test_next_hex_u64() {
+        let mut reader = Reader::from_str("0x1F 1f 0 0X0 FFFFFFFFFFFFFFFF 0x g");
+        assert_eq!(reader.next_hex_u64(), Some(0x1f));
+        assert_eq!(reader.next_hex_u64(), Some(0x1f));
+        assert_eq!(reader.next_hex_u64(), Some(0));
+        assert_eq!(reader.next_hex_u64(), Some(0));
+        assert_eq!(reader.next_hex_u64(), Some(u64::MAX));
+        assert_eq!(reader.next_hex_u64(), None);
+        assert_eq!(reader.next_hex_u64(), None);
+    }
+
+    #[test]
+    fn test_next_radix() {
+        let mut reader = Reader::from_str("777 1010 Zz 12 9");
+        assert_eq!(reader.next_radix(8), Some(0o777));
+        assert_eq!(reader.next_radix(2), Some(10));
+        assert_eq!(reader.next_radix(36), Some(35 * 36 + 35));
+        assert_eq!(reader.next_radix(3), Some(5));
+        assert_eq!(reader.next_radix(9), None);
+
+        let mut reader = Reader::from_str("3w5e11264sgsf 3w5e11264sgsg 1");
+        assert_eq!(reader.next_radix(36), Some(u64::MAX));
+        assert_eq!(reader.next_radix(36), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Overflow));
+        assert_eq!(reader.next_radix(36), Some(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_next_radix_invalid_base() {
+        Reader::from_str("1").next_radix(37);
+    }
+
+    #[test]
+    fn test_next_binary() {
+        let mut reader = Reader::from_str("101 0001\n2");
+        assert_eq!(reader.next_binary_u64(), Some(5));
+        assert_eq!(reader.next_binary_u64(), Some(1));
+        assert_eq!(reader.next_binary_u64(), None);
+
+        let bits = "1".repeat(64) + " 1" + &"0".repeat(64) + " 1";
+        let mut reader = Reader::from_str(&bits);
+        assert_eq!(reader.next_binary_u64(), Some(u64::MAX));
+        assert_eq!(reader.next_binary_u64(), None);
+        assert_eq!(reader.last_error(), Some(ReadErrorKind::Overflow));
+        assert_eq!(reader.next_binary_u64(), Some(1));
+
+        let bits = "1".repeat(64) + "01";
+        let mut reader = Reader::from_str(&bits);
+        assert_eq!(reader.next_bitset(), Some(vec![u64::MAX, 0b10]));
+        assert_eq!(reader.next_bitset(), None);
+    }
+
+    #[test]
+    fn test_next_digits() {
+        let digits = "9".repeat(1000);
+        let mut reader = Reader::from_str(&format!("{digits} +05 - 7"));
+        assert_eq!(reader.next_digits(), Some(vec![9; 1000]));
+        assert_eq!(reader.next_digits(), Some(vec![0, 5]));
+        assert_eq!(reader.next_digits(), None);
+        assert_eq!(reader.next_digits(), Some(vec![7]));
+    }
+
+    #[test]
+    fn test_next_time() {
+        let mut reader = Reader::from_str("00:00:01 23:59:59 12:30 7:5 12-30");
+        assert_eq!(reader.next_hhmmss(), Some(1));
+        assert_eq!(reader.next_hhmmss(), Some(86399));
+        assert_eq!(reader.next_hhmm(), Some(750));
+        assert_eq!(reader.next_hhmm(), Some(425));
+        assert_eq!(reader.next_hhmm(), None);
+    }
+
+    #[test]
+    fn test_next_bool() {
+        let mut reader = Reader::from_str("1 0 true FALSE Yes no maybe");
+        assert_eq!(reader.next_bool(), Some(true));
+        assert_eq!(reader.next_bool(), Some(false));
+        assert_eq!(reader.next_bool(), Some(true));
+        assert_eq!(reader.next_bool(), Some(false));
+        assert_eq!(reader.next_bool(), Some(true));
+        assert_eq!(reader.next_bool(), Some(false));
+        assert_eq!(reader.next_bool(), None);
+    }
+
+    #[test]
+    fn test_next_token() {
+        let mut reader = Reader::from_str("abc  de\n\tf\r\ngh ");
+        assert_eq!(reader.next_token().unwrap(), "abc");
+        assert_eq!(reader.next_token().unwrap(), "de");
+        assert_eq!(reader.next_token().unwrap(), "f");
+        assert_eq!(reader.next_token().unwrap(), "gh");
+        assert_eq!(reader.next_token(), None);
+    }
+
+    #[test]
+    fn test_next_token_bytes() {
+        let mut reader = Reader::from_str("ab\n  cde f");
+        assert_eq!(reader.next_token_bytes().unwrap(), b"ab");
+        assert_eq!(reader.next_token_str().unwrap(), "cde");
+        assert_eq!(reader.next_token_str().unwrap(), "f");
+        assert_eq!(reader.next_token_bytes(), None);
+    }
+
+    #[test]
+    fn test_next_chars_and_bytes() {
+        let mut reader = Reader::from_str("#.# ab\nxyz");
+        assert_eq!(reader.next_bytes().unwrap(), b"#.#");
+        assert_eq!(reader.next_chars().unwrap(), vec!['a', 'b']);
+        assert_eq!(reader.next_chars().unwrap(), vec!['x', 'y', 'z']);
+        assert_eq!(reader.next_bytes(), None);
+    }
+
+    #[test]
+    fn test_next_exact() {
+        let mut reader = Reader::from_str("abcd ab abc");
+        assert_eq!(reader.next_exact(4).unwrap(), b"abcd");
+        assert_eq!(reader.next_exact(3), None);
+        assert_eq!(reader.next_exact(3).unwrap(), b"abc");
+    }
+
+    #[test]
+    fn test_next_parse() {
+        let mut reader = Reader::from_str("42 -7.5 true x1");
+        assert_eq!(reader.next_parse::<u16>(), Some(42));
+        assert_eq!(reader.next_parse::<f32>(), Some(-7.5));
+        assert_eq!(reader.next_parse::<bool>(), Some(true));
+        assert_eq!(reader.next_parse::<i32>(), None);
+    }
+
+    #[test]
+    fn test_next_line() {
+        let mut reader = Reader::from_str("the first line\n\nwindows\r\nlast");
+        assert_eq!(reader.next_line().unwrap(), "the first line");
+        assert_eq!(reader.next_line().unwrap(), "");
+        assert_eq!(reader.next_line().unwrap(), "windows");
+        assert_eq!(reader.next_line().unwrap(), "last");
+        assert_eq!(reader.next_line(), None);
+    }
+
+    #[test]
+    fn test_skip() {
+        let mut reader = Reader::from_str("a b c 1\nthe rest\n\n2 x");
+        assert_eq!(reader.skip_tokens(3), Some(()));
+        assert_eq!(reader.next_i32(), Some(1));
+        assert_eq!(reader.skip_line(), Some(()));
+        assert_eq!(reader.skip_line(), Some(()));
+        assert_eq!(reader.next_i32(), Some(2));
+        assert_eq!(reader.skip_tokens(2), None);
+        assert_eq!(reader.skip_line(), None);
+    }
+
+    #[test]
+    fn test_blocks() {
+        let mut reader = Reader::from_str("\nname one\n1 2\n\n\nname two\r\n  \nlast\n");
+        assert_eq!(reader.read_until_blank_line().unwrap(), vec!["name one", "1 2"]);
+        let blocks: Vec<_> = reader.blocks().collect();
+        assert_eq!(blocks, vec![vec!["name two"], vec!["last"]]);
+        assert_eq!(reader.read_until_blank_line(), None);
+    }
+
+    #[test]
+    fn test_line_col() {
+        let mut reader = Reader::from_str("10 20\nabc\n\n  x");
+        assert_eq!(reader.line_col(), Position { line: 1, column: 1 });
+        reader.next_i32();
+        assert_eq!(reader.line_col(), Position { line: 1, column: 4 });
+        reader.next_i32();
+        assert_eq!(reader.line_col(), Position { line: 2, column: 1 });
+        reader.skip_line();
+        reader.skip_line();
+        reader.next_char();
+        assert_eq!(reader.line_col(), Position { line: 4, column: 2 });
+        reader.skip_token();
+        assert_eq!(reader.line_col(), Position { line: 4, column: 4 });
+        // through `&mut`, where `position` would have been `Iterator::position`
+        let reader = &mut reader;
+        assert_eq!(reader.line_col(), Position { line: 4, column: 4 });
+    }
+
+    #[test]
+    fn test_read_rest() {
+        let mut reader = Reader::from_str("12 rest of\nthe input\n");
+        assert_eq!(reader.next_token().unwrap(), "12");
+        assert_eq!(reader.read_rest_bytes(), b"rest of\nthe input\n");
+        assert_eq!(reader.read_rest_bytes(), b"");
+        assert_eq!(reader.next_token(), None);
+    }
+
+    #[test]
+    fn test_crlf() {
+        let mut reader = Reader::from_str("12\r\n-3\r\n4.5\r\nword\r\nnext line\r\nab\r\n");
+        assert_eq!(reader.next_i32(), Some(12));
+        assert_eq!(reader.next_i64(), Some(-3));
+        assert_eq!(reader.next_f64(), Some(4.5));
+        assert_eq!(reader.next_token().unwrap(), "word");
+        assert_eq!(reader.next_line().unwrap(), "next line");
+        assert_eq!(reader.next_char(), Some('a'));
+        assert_eq!(reader.next_char(), Some('b'));
+        assert_eq!(reader.next_char(), Some('\n'));
+        assert_eq!(reader.next_char(), None);
+    }
+
     #[test]
     fn test_long_float() {
         let mut reader = Reader::from_str("4.323580432456786");
         assert_eq!(reader.next_f64().unwrap(), 4.323580432456786);
     }
+
+    #[test]
+    fn test_next_f64_matches_parse() {
+        let mut inputs = vec![
+            "0.1", "-0.5", "-0", "0.30000000000000004", "9007199254740993", ".25", "7.",
+            "123456789012345678901234567890.5", "0.000000000000000000000000000123",
+            "1.7976931348623157", "0.1000000000000000055511151231257827021181583404541015625",
+            "2.2250738585072013830902327173324040642192159804623318306e-308", "1.7976931348623157e308",
+            "1.5e-9", "3E+7", "4e22", "4e23", "1e400", "1e-400", "123.456e-2", "5e0",
+        ];
+        let mut generated = Vec::new();
+        let mut seed: u64 = 0x2545F4914F6CDD1D;
+        for _ in 0..2000 {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            let point = (seed % 21) as usize;
+            let digits = format!("{:020}", seed >> 1);
+            generated.push(format!("{}.{}", &digits[..point], &digits[point..]));
+            generated.push(format!("{}e{}", &digits[..point.max(1)], (seed % 601) as i32 - 300));
+        }
+        inputs.extend(generated.iter().map(String::as_str));
+
+        let mut reader = Reader::from_str(&inputs.join(" "));
+        for input in inputs {
+            let expected: f64 = input.parse().unwrap();
+            assert_eq!(reader.next_f64().unwrap().to_bits(), expected.to_bits(), "{input}");
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,181 @@
+/// Reads several values of mixed types from a `Reader` in one statement, declaring a variable for each
+///
+/// The format string documents the expected layout, with one `{}` per value. Values are read in
+/// order with `Reader::next_t`, so they can be separated by any single delimiter byte.
+///
+/// # Panics:
+///
+/// Panics if any of the values can't be read
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::scan;
+///
+/// let mut reader = comp_io::Reader::from_str("3 -7 2.5 name");
+/// scan!(reader, "{} {} {} {}", n: usize, k: i64, x: f64, s: String);
+/// assert_eq!((n, k, x, s.as_str()), (3, -7, 2.5, "name"));
+/// ```
+#[macro_export]
+macro_rules! scan {
+    ($reader:expr, $format:literal, $($name:ident : $t:ty),+ $(,)?) => {
+        let ($($name,)+) = {
+            debug_assert_eq!(
+                $format.matches("{}").count(),
+                [$(stringify!($name)),+].len(),
+                "scan! format {:?} doesn't match the number of values",
+                $format,
+            );
+            $reader
+                .next_t::<($($t,)+)>()
+                .expect(concat!("scan! failed to read \"", $format, "\""))
+        };
+    };
+}
+
+/// Writes formatted output to a `Writer`, like `print!` but buffered
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::out;
+///
+/// let mut writer = comp_io::Writer::new();
+/// out!(writer, "{} {:.2}", 7, 0.5);
+/// ```
+#[macro_export]
+macro_rules! out {
+    ($writer:expr, $($arg:tt)*) => {
+        $writer.write_fmt(format_args!($($arg)*))
+    };
+}
+
+/// Writes formatted output followed by a `\n` to a `Writer`, like `println!` but buffered
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::outln;
+///
+/// let mut writer = comp_io::Writer::new();
+/// outln!(writer, "Case #{}: {}", 1, "yes");
+/// outln!(writer);
+/// ```
+#[macro_export]
+macro_rules! outln {
+    ($writer:expr) => {
+        $writer.newline()
+    };
+    ($writer:expr, $($arg:tt)*) => {{
+        $writer.write_fmt(format_args!($($arg)*));
+        $writer.newline();
+    }};
+}
+
+/// Writes a `Case #i: ` prefixed line to a `Writer`, in the format of Code Jam style problems
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::case;
+///
+/// let mut writer = comp_io::Writer::in_memory();
+/// for (i, answer) in [7, 3].iter().enumerate() {
+///     case!(writer, i + 1, "{}", answer);
+/// }
+/// assert_eq!(writer.into_string(), "Case #1: 7\nCase #2: 3\n");
+/// ```
+#[macro_export]
+macro_rules! case {
+    ($writer:expr, $i:expr, $($arg:tt)*) => {{
+        $writer.case($i);
+        $writer.write_fmt(format_args!($($arg)*));
+        $writer.newline();
+    }};
+}
+
+/// Reads a value from the thread's global `Io`, like `Reader::next_t`
+///
+/// `read!(T)` reads one `T`, `read!(A, B, ..)` reads a tuple, and `read!([T; n])` reads a `Vec` of `n` values
+///
+/// # Panics:
+///
+/// Panics if the value can't be read
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::read;
+///
+/// # let input: Box<dyn std::io::Read> = Box::new(std::io::Cursor::new("2 a\n4 5"));
+/// # comp_io::Io { reader: comp_io::Reader::from_reader(input), writer: comp_io::Writer::auto() }.install();
+/// let (n, c) = read!(usize, char);
+/// let a = read!([u32; n]);
+/// assert_eq!((c, a), ('a', vec![4, 5]));
+/// ```
+#[macro_export]
+macro_rules! read {
+    ([$t:ty; $n:expr]) => {{
+        let n = $n;
+        $crate::Io::with(|io| io.reader.next_vec::<$t>(n))
+            .expect(concat!("read! failed to read [", stringify!($t), "; ", stringify!($n), "]"))
+    }};
+    ($t:ty) => {
+        $crate::Io::with(|io| io.reader.next_t::<$t>()).expect(concat!("read! failed to read ", stringify!($t)))
+    };
+    ($($t:ty),+ $(,)?) => {
+        $crate::Io::with(|io| io.reader.next_t::<($($t,)+)>())
+            .expect(concat!("read! failed to read (", stringify!($($t),+), ")"))
+    };
+}
+
+/// Writes values separated by spaces and followed by a `\n` to the thread's global `Io`
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::put;
+///
+/// put!("YES");
+/// put!(3, -1.5, 'c');
+/// put!();
+/// ```
+#[macro_export]
+macro_rules! put {
+    () => {
+        $crate::Io::with(|io| io.writer.newline())
+    };
+    ($first:expr $(, $rest:expr)* $(,)?) => {{
+        // each value is evaluated before borrowing the `Io`, so it can itself use `read!`
+        let value = $first;
+        $crate::Io::with(|io| io.writer.write(value));
+        $(
+            let value = $rest;
+            $crate::Io::with(|io| {
+                io.writer.write_str(" ");
+                io.writer.write(value);
+            });
+        )*
+        $crate::Io::with(|io| io.writer.newline());
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Reader;
+
+    #[test]
+    fn test_scan() {
+        let mut reader = Reader::from_str("5 abc -1.5\n12:30");
+        scan!(reader, "{} {} {}", n: u8, s: String, x: f64);
+        scan!(&mut reader, "{}:{}", h: u32, m: u32);
+        assert_eq!((n, s.as_str(), x, h, m), (5, "abc", -1.5, 12, 30));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_scan_fails() {
+        let mut reader = Reader::from_str("x");
+        scan!(reader, "{}", _n: u8);
+    }
+}
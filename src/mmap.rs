@@ -0,0 +1,130 @@
+use std::ffi::c_void;
+use std::fs::File;
+use std::io::{self, Read};
+use std::mem::ManuallyDrop;
+use std::ops::Deref;
+use std::os::raw::c_int;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::path::Path;
+use std::{ptr, slice};
+
+use crate::buffer::Buffer;
+use crate::Reader;
+
+const PROT_READ: c_int = 1;
+const MAP_PRIVATE: c_int = 2;
+
+// `off_t` is only always 64 bits on 64-bit targets, which is why the module is limited to them
+extern "C" {
+    fn mmap(addr: *mut c_void, len: usize, prot: c_int, flags: c_int, fd: c_int, offset: i64) -> *mut c_void;
+    fn munmap(addr: *mut c_void, len: usize) -> c_int;
+}
+
+/// A read-only private mapping of a whole file, unmapped on drop
+pub(crate) struct Mmap {
+    ptr: *mut c_void,
+    len: usize,
+}
+
+impl Mmap {
+    /// Maps the first `len` bytes of `file`, which must not be empty
+    fn map(file: &File, len: usize) -> io::Result<Self> {
+        // SAFETY: a fresh private read-only mapping of a valid fd, no existing memory is touched
+        let ptr = unsafe { mmap(ptr::null_mut(), len, PROT_READ, MAP_PRIVATE, file.as_raw_fd(), 0) };
+        if ptr as isize == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(Mmap { ptr, len })
+    }
+}
+
+// SAFETY: the mapping is read-only, so sharing or moving it across threads is fine
+unsafe impl Send for Mmap {}
+unsafe impl Sync for Mmap {}
+
+impl Deref for Mmap {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the mapping is `len` readable bytes that live until drop
+        unsafe { slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+impl Drop for Mmap {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` and `len` are exactly what `mmap` returned
+        unsafe { munmap(self.ptr, self.len) };
+    }
+}
+
+/// Maps `file` into a reader, or reads it into memory if it can't be mapped (a pipe, or an empty file)
+fn map_or_read(mut file: &File) -> io::Result<Reader<io::Empty>> {
+    let metadata = file.metadata()?;
+    let len = metadata.len() as usize;
+    if metadata.is_file() && len > 0 {
+        if let Ok(map) = Mmap::map(file, len) {
+            let mut reader = Reader::from(Vec::new());
+            reader.buffer = Buffer::Mapped(map);
+            reader.len = len;
+            return Ok(reader);
+        }
+    }
+    let mut input = Vec::new();
+    file.read_to_end(&mut input)?;
+    Ok(Reader::from(input))
+}
+
+impl Reader<io::Empty> {
+    /// Memory-maps the file at `path` and parses straight from the mapping, without copying it into a buffer.
+    /// Requires the `mmap` feature and a 64-bit unix target
+    ///
+    /// The file must not be modified while the reader is alive
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// # let path = std::env::temp_dir().join("comp_io_mmap_file.txt");
+    /// # std::fs::write(&path, "2\n10 20\n").unwrap();
+    /// let mut reader = comp_io::Reader::mmap_file(&path).unwrap();
+    /// let n = reader.next_usize().unwrap();
+    /// let a: Vec<i32> = reader.next_vec(n).unwrap();
+    /// assert_eq!(a, vec![10, 20]);
+    /// ```
+    pub fn mmap_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        map_or_read(&File::open(path)?)
+    }
+
+    /// Memory-maps stdin when it is redirected from a file, and reads all of it into memory otherwise.
+    /// Requires the `mmap` feature and a 64-bit unix target
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// let mut reader = comp_io::Reader::mmap_stdin().unwrap();
+    /// let n = reader.next_usize().unwrap();
+    /// ```
+    pub fn mmap_stdin() -> io::Result<Self> {
+        // SAFETY: the fd stays owned by stdin, `ManuallyDrop` keeps it from being closed here
+        let file = ManuallyDrop::new(unsafe { File::from_raw_fd(io::stdin().as_raw_fd()) });
+        map_or_read(&file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mmap_file() {
+        let path = std::env::temp_dir().join("comp_io_test_mmap_file.txt");
+        std::fs::write(&path, "3 -7\nabc\n").unwrap();
+        let mut reader = Reader::mmap_file(&path).unwrap();
+
+        assert!(matches!(reader.buffer, Buffer::Mapped(_)));
+        assert_eq!(reader.next_pair(), Some((3, -7)));
+        assert_eq!(reader.next_token(), Some("abc".to_string()));
+        assert_eq!(reader.next_token(), None);
+    }
+}
@@ -0,0 +1,103 @@
+use std::io::{self, Read};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use crate::Reader;
+
+/// A source that is read on a background thread, so the next chunk is already loaded while the current one is parsed
+///
+/// Created by `Reader::with_prefetch`
+pub struct Prefetch {
+    chunks: Receiver<Vec<u8>>,
+    chunk: Vec<u8>,
+    pos: usize,
+}
+
+impl Prefetch {
+    /// Starts reading `source` in chunks of `capacity` bytes, the same amount the reader asks for on each refill
+    fn spawn<R: Read + Send + 'static>(mut source: R, capacity: usize) -> Self {
+        // a single slot, so at most one chunk is read ahead of the one being parsed
+        let (sender, chunks) = mpsc::sync_channel(1);
+        thread::spawn(move || loop {
+            let mut chunk = Vec::with_capacity(capacity);
+            match (&mut source).take(capacity as u64).read_to_end(&mut chunk) {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if sender.send(chunk).is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        Prefetch {
+            chunks,
+            chunk: Vec::new(),
+            pos: 0,
+        }
+    }
+}
+
+impl Read for Prefetch {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.chunk.len() {
+            match self.chunks.recv() {
+                Ok(chunk) => {
+                    self.chunk = chunk;
+                    self.pos = 0;
+                }
+                Err(_) => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.chunk.len() - self.pos);
+        buf[..n].copy_from_slice(&self.chunk[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl<R: Read + Send + 'static> Reader<R> {
+    /// Moves reading from the source onto a background thread, overlapping the wait for input with parsing
+    ///
+    /// Input that was already buffered is kept, and chunks are read with the reader's capacity. Mostly useful for
+    /// very large inputs. The source has to be `Send`, so stdin should be passed in as
+    /// `Reader::from_reader(std::io::stdin())` rather than locked by `Reader::new`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let input = std::io::Cursor::new("3 1 4 1");
+    /// let mut reader = comp_io::Reader::from_reader(input).with_prefetch();
+    /// let n = reader.next_usize().unwrap();
+    /// let a: Vec<i32> = reader.next_vec(n).unwrap();
+    /// assert_eq!(a, vec![1, 4, 1]);
+    /// ```
+    pub fn with_prefetch(self) -> Reader<Prefetch> {
+        let capacity = self.capacity;
+        self.map_source(|source| Prefetch::spawn(source, capacity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prefetch_across_chunks() {
+        let input: String = (0..200_000).map(|i| format!("{i}\n")).collect();
+        let mut reader = Reader::from_reader(io::Cursor::new(input)).with_prefetch();
+
+        for i in 0..200_000 {
+            assert_eq!(reader.next_u32(), Some(i));
+        }
+        assert_eq!(reader.next_u32(), None);
+    }
+
+    #[test]
+    fn test_prefetch_capacity() {
+        let mut reader = Reader::builder().capacity(3).source(io::Cursor::new("12 345\n6")).with_prefetch();
+        reader.next_u32();
+        assert_eq!(reader.source.chunk.capacity(), 3);
+        assert_eq!(reader.next_u32(), Some(345));
+        assert_eq!(reader.next_u32(), Some(6));
+    }
+}
@@ -0,0 +1,636 @@
+use std::io::Read;
+use std::iter::Take;
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::str::FromStr;
+
+use crate::{ReadErrorKind, Reader};
+
+/// A type that can be read from a `Reader`, used by `Reader::next_t`
+///
+/// Implemented for the primitive numeric types, `bool`, `char`, `String`, tuples, and `Vec<T>`,
+/// which reads a length followed by that many values. Implement it for your own types to read
+/// them with the same API
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::{Reader, Readable};
+/// use std::io::Read;
+///
+/// struct Point {
+///     x: i64,
+///     y: i64,
+/// }
+///
+/// impl Readable for Point {
+///     fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+///         Some(Point { x: reader.next_t()?, y: reader.next_t()? })
+///     }
+/// }
+///
+/// let mut reader = Reader::from_str("3 -4");
+/// let p: Point = reader.next_t().unwrap();
+/// assert_eq!((p.x, p.y), (3, -4));
+/// ```
+pub trait Readable: Sized {
+    /// Reads one value, returning `None` if the input ends or is malformed
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self>;
+}
+
+/// An enum that is read from a keyword token, such as `ADD` or `QUERY`, used by `Reader::next_enum`
+///
+/// Usually derived with `#[derive(ReadableEnum)]` from the `derive` feature, which also implements `Readable`
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::ReadableEnum;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Op {
+///     Push,
+///     Pop,
+/// }
+///
+/// impl ReadableEnum for Op {
+///     fn from_token(token: &[u8]) -> Option<Self> {
+///         match token {
+///             b"push" => Some(Op::Push),
+///             b"pop" => Some(Op::Pop),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let mut reader = comp_io::Reader::from_str("push pop");
+/// assert_eq!(reader.next_enum(), Some(Op::Push));
+/// assert_eq!(reader.next_enum(), Some(Op::Pop));
+/// ```
+pub trait ReadableEnum: Sized {
+    /// Returns the variant named by `token`, or `None` if it doesn't name one
+    fn from_token(token: &[u8]) -> Option<Self>;
+}
+
+/// Implements `Readable` by forwarding to an existing `Reader` method
+macro_rules! impl_readable {
+    ($($t:ty => $method:ident),* $(,)?) => {
+        $(
+            impl Readable for $t {
+                fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+                    reader.$method()
+                }
+            }
+        )*
+    };
+}
+
+impl_readable!(
+    i8 => next_i8,
+    i16 => next_i16,
+    i32 => next_i32,
+    i64 => next_i64,
+    i128 => next_i128,
+    u8 => next_u8,
+    u16 => next_u16,
+    u32 => next_u32,
+    u64 => next_u64,
+    u128 => next_u128,
+    usize => next_usize,
+    f64 => next_f64,
+    bool => next_bool,
+    String => next_token,
+);
+
+impl Readable for isize {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        Some(reader.next_i64()? as isize)
+    }
+}
+
+impl Readable for f32 {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        Some(reader.next_f64()? as f32)
+    }
+}
+
+/// Reads a single non-whitespace character, so both `a b c` and `abc` read as three chars
+impl Readable for char {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        let c = reader.skip_whitespace()?;
+        reader.skip_delimiter();
+        Some(c as char)
+    }
+}
+
+/// Reads a length `n`, followed by `n` values
+impl<T: Readable> Readable for Vec<T> {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        let n = reader.next_usize()?;
+        reader.next_vec(n)
+    }
+}
+
+/// Reads `N` values directly onto the stack
+impl<T: Readable, const N: usize> Readable for [T; N] {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        // stop reading after the first failure, since `map` visits every element
+        let mut failed = false;
+        let values = [(); N].map(|_| {
+            let value = if failed { None } else { T::read(reader) };
+            failed |= value.is_none();
+            value
+        });
+        if failed {
+            return None;
+        }
+        Some(values.map(Option::unwrap))
+    }
+}
+
+/// Implements `Readable` for a tuple, reading its elements in order
+macro_rules! impl_readable_tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Readable),+> Readable for ($($name,)+) {
+            fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+                Some(($($name::read(reader)?,)+))
+            }
+        }
+    };
+}
+
+impl_readable_tuple!(A);
+impl_readable_tuple!(A, B);
+impl_readable_tuple!(A, B, C);
+impl_readable_tuple!(A, B, C, D);
+impl_readable_tuple!(A, B, C, D, E);
+impl_readable_tuple!(A, B, C, D, E, F);
+impl_readable_tuple!(A, B, C, D, E, F, G);
+impl_readable_tuple!(A, B, C, D, E, F, G, H);
+impl_readable_tuple!(A, B, C, D, E, F, G, H, I);
+impl_readable_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_readable_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_readable_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// A 1-indexed value in the input, converted to 0-indexed when read. A `0` in the input fails to read
+///
+/// # Example:
+///
+/// ```
+/// use comp_io::Usize1;
+///
+/// let mut reader = comp_io::Reader::from_str("1 3");
+/// let (Usize1(u), Usize1(v)) = reader.next_t().unwrap();
+/// assert_eq!((u, v), (0, 2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Usize1(pub usize);
+
+impl Readable for Usize1 {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        let value = reader.next_usize()?.checked_sub(1);
+        if value.is_none() {
+            reader.failure = Some(ReadErrorKind::OutOfRange);
+        }
+        Some(Usize1(value?))
+    }
+}
+
+/// A whitespace-delimited token read as a `Vec<char>`, see `Reader::next_chars`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Chars(pub Vec<char>);
+
+impl Readable for Chars {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        reader.next_chars().map(Chars)
+    }
+}
+
+impl Deref for Chars {
+    type Target = Vec<char>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A whitespace-delimited token read as a `Vec<u8>`, see `Reader::next_bytes`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Readable for Bytes {
+    fn read<R: Read>(reader: &mut Reader<R>) -> Option<Self> {
+        reader.next_bytes().map(Bytes)
+    }
+}
+
+impl Deref for Bytes {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// An iterator over values read from a `Reader`, created by `Reader::iter`
+///
+/// Ends at the end of input, or at the first value that fails to parse
+pub struct Iter<'a, T, R> {
+    reader: &'a mut Reader<R>,
+    marker: PhantomData<T>,
+}
+
+impl<T: Readable, R: Read> Iterator for Iter<'_, T, R> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        T::read(self.reader)
+    }
+}
+
+impl<R: Read> Reader<R> {
+    /// Reads the next value of any `Readable` type from stdin
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("5 -2.5 word");
+    /// let n: usize = reader.next_t().unwrap();
+    /// let (x, s) = reader.next_t::<(f64, String)>().unwrap();
+    /// assert_eq!((n, x, s.as_str()), (5, -2.5, "word"));
+    /// ```
+    pub fn next_t<T: Readable>(&mut self) -> Option<T> {
+        T::read(self)
+    }
+
+    /// Reads the next value from stdin, mapping `sentinel` to `Some(None)`
+    ///
+    /// The outer `Option` is `None` if the read itself fails
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("-1 4");
+    /// assert_eq!(reader.next_option(-1), Some(None));
+    /// assert_eq!(reader.next_option(-1), Some(Some(4)));
+    /// ```
+    pub fn next_option<T: Readable + PartialEq>(&mut self, sentinel: T) -> Option<Option<T>> {
+        let value = T::read(self)?;
+        Some((value != sentinel).then_some(value))
+    }
+
+    /// Reads the next i64 from stdin, mapping `sentinel` (such as `-1` for a missing parent) to `Some(None)`
+    pub fn next_option_i64(&mut self, sentinel: i64) -> Option<Option<i64>> {
+        self.next_option(sentinel)
+    }
+
+    /// Reads the next token from stdin, mapping the token `sentinel` to `Some(None)`, and parsing it otherwise
+    ///
+    /// Useful for wildcards like `?` that aren't a valid value of the type
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("? 12");
+    /// assert_eq!(reader.next_option_token::<u32>("?"), Some(None));
+    /// assert_eq!(reader.next_option_token::<u32>("?"), Some(Some(12)));
+    /// ```
+    pub fn next_option_token<T: FromStr>(&mut self, sentinel: &str) -> Option<Option<T>> {
+        let token = self.next_token_str()?;
+        if token == sentinel {
+            return Some(None);
+        }
+        match token.parse() {
+            Ok(value) => Some(Some(value)),
+            Err(_) => {
+                self.failure = Some(ReadErrorKind::Invalid);
+                None
+            }
+        }
+    }
+
+    /// Returns an iterator over the remaining values in stdin
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("3 1 4 1 5");
+    /// assert_eq!(reader.iter::<i64>().max(), Some(5));
+    /// ```
+    pub fn iter<T: Readable>(&mut self) -> Iter<'_, T, R> {
+        Iter {
+            reader: self,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the next `n` values in stdin
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("2 10 20 30");
+    /// let n = reader.next_usize().unwrap();
+    /// assert_eq!(reader.take_n::<u32>(n).sum::<u32>(), 30);
+    /// ```
+    pub fn take_n<T: Readable>(&mut self, n: usize) -> Take<Iter<'_, T, R>> {
+        self.iter().take(n)
+    }
+
+    /// Reads the next whitespace-delimited token from stdin as a `ReadableEnum` variant
+    pub fn next_enum<T: ReadableEnum>(&mut self) -> Option<T> {
+        T::from_token(self.next_token_bytes()?)
+    }
+
+    /// Reads the next `n` values from stdin into a `Vec`, allocating once
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("3\n10 20 30");
+    /// let n = reader.next_usize().unwrap();
+    /// let a: Vec<i64> = reader.next_vec(n).unwrap();
+    /// assert_eq!(a, vec![10, 20, 30]);
+    /// ```
+    pub fn next_vec<T: Readable>(&mut self, n: usize) -> Option<Vec<T>> {
+        let mut values = Vec::with_capacity(n);
+        for _ in 0..n {
+            values.push(T::read(self)?);
+        }
+        Some(values)
+    }
+
+    /// Reads `out.len()` values from stdin into an existing slice, without allocating
+    ///
+    /// Returns `None` if the input ends first, leaving the rest of the slice partially overwritten
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1 2 3\n4 5 6");
+    /// let mut row = [0i32; 3];
+    /// reader.fill_slice(&mut row).unwrap();
+    /// reader.fill_slice(&mut row).unwrap();
+    /// assert_eq!(row, [4, 5, 6]);
+    /// ```
+    pub fn fill_slice<T: Readable>(&mut self, out: &mut [T]) -> Option<()> {
+        for slot in out {
+            *slot = T::read(self)?;
+        }
+        Some(())
+    }
+
+    /// Reads a length `k` from stdin, followed by `k` values
+    pub fn next_prefixed_vec<T: Readable>(&mut self) -> Option<Vec<T>> {
+        self.next_t()
+    }
+
+    /// Reads `rows` length-prefixed lists from stdin, e.g. adjacency lists given as `k v_1 ... v_k`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("2 1 2\n0\n1 3");
+    /// let lists: Vec<Vec<usize>> = reader.next_jagged(3).unwrap();
+    /// assert_eq!(lists, vec![vec![1, 2], vec![], vec![3]]);
+    /// ```
+    pub fn next_jagged<T: Readable>(&mut self, rows: usize) -> Option<Vec<Vec<T>>> {
+        (0..rows).map(|_| self.next_prefixed_vec()).collect()
+    }
+
+    /// Reads values from stdin until one matches `is_sentinel`, which is consumed but not included
+    ///
+    /// Also stops at the end of input
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("4 8 15 0 16");
+    /// let values: Vec<u32> = reader.next_until_sentinel(|&x| x == 0);
+    /// assert_eq!(values, vec![4, 8, 15]);
+    /// ```
+    pub fn next_until_sentinel<T: Readable>(&mut self, is_sentinel: impl Fn(&T) -> bool) -> Vec<T> {
+        let mut values = Vec::new();
+        while let Some(value) = self.next_t().filter(|value| !is_sentinel(value)) {
+            values.push(value);
+        }
+        values
+    }
+
+    /// Reads pairs from stdin until the pair equal to `sentinel`, such as `(0, 0)`, which is not included
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1 2\n3 4\n0 0\n");
+    /// assert_eq!(reader.next_pairs_until((0, 0)), vec![(1, 2), (3, 4)]);
+    /// ```
+    pub fn next_pairs_until<A, B>(&mut self, sentinel: (A, B)) -> Vec<(A, B)>
+    where
+        A: Readable + PartialEq,
+        B: Readable + PartialEq,
+    {
+        self.next_until_sentinel(|pair| *pair == sentinel)
+    }
+
+    /// Reads `n` rows of pairs from stdin into two parallel `Vec`s, one per column
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1 10\n2 20\n3 30");
+    /// let (xs, ys) = reader.next_columns2::<u32, i64>(3).unwrap();
+    /// assert_eq!((xs, ys), (vec![1, 2, 3], vec![10, 20, 30]));
+    /// ```
+    pub fn next_columns2<A: Readable, B: Readable>(&mut self, n: usize) -> Option<(Vec<A>, Vec<B>)> {
+        let (mut a, mut b) = (Vec::with_capacity(n), Vec::with_capacity(n));
+        for _ in 0..n {
+            a.push(A::read(self)?);
+            b.push(B::read(self)?);
+        }
+        Some((a, b))
+    }
+
+    /// Reads `n` rows of triples from stdin into three parallel `Vec`s, one per column
+    pub fn next_columns3<A: Readable, B: Readable, C: Readable>(
+        &mut self,
+        n: usize,
+    ) -> Option<(Vec<A>, Vec<B>, Vec<C>)> {
+        let (mut a, mut b, mut c) = (Vec::with_capacity(n), Vec::with_capacity(n), Vec::with_capacity(n));
+        for _ in 0..n {
+            a.push(A::read(self)?);
+            b.push(B::read(self)?);
+            c.push(C::read(self)?);
+        }
+        Some((a, b, c))
+    }
+
+    /// Reads a `rows` by `cols` matrix from stdin, one `Vec` per row
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1 2 3\n4 5 6");
+    /// let grid: Vec<Vec<i32>> = reader.next_matrix(2, 3).unwrap();
+    /// assert_eq!(grid[1][2], 6);
+    /// ```
+    pub fn next_matrix<T: Readable>(&mut self, rows: usize, cols: usize) -> Option<Vec<Vec<T>>> {
+        (0..rows).map(|_| self.next_vec(cols)).collect()
+    }
+
+    /// Reads a `rows` by `cols` matrix from stdin into a single row-major `Vec`, returned with its stride
+    ///
+    /// Element `(i, j)` is at index `i * stride + j`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1 2 3\n4 5 6");
+    /// let (grid, stride) = reader.next_matrix_flat::<i32>(2, 3).unwrap();
+    /// assert_eq!(grid[stride + 2], 6);
+    /// ```
+    pub fn next_matrix_flat<T: Readable>(&mut self, rows: usize, cols: usize) -> Option<(Vec<T>, usize)> {
+        Some((self.next_vec(rows * cols)?, cols))
+    }
+
+    /// Reads the next `N` values from stdin into an array, without allocating
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut reader = comp_io::Reader::from_str("1 2 3");
+    /// let [x, y, z] = reader.next_array::<i32, 3>().unwrap();
+    /// assert_eq!((x, y, z), (1, 2, 3));
+    /// ```
+    pub fn next_array<T: Readable, const N: usize>(&mut self) -> Option<[T; N]> {
+        self.next_t()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_t() {
+        let mut reader = Reader::from_str("-7 200 3.5 x yes hello 3 1 2 3 -1 z");
+        assert_eq!(reader.next_t::<i64>(), Some(-7));
+        assert_eq!(reader.next_t::<u8>(), Some(200));
+        assert_eq!(reader.next_t::<f32>(), Some(3.5));
+        assert_eq!(reader.next_t::<char>(), Some('x'));
+        assert_eq!(reader.next_t::<bool>(), Some(true));
+        assert_eq!(reader.next_t::<String>().unwrap(), "hello");
+        assert_eq!(reader.next_t::<Vec<u32>>(), Some(vec![1, 2, 3]));
+        assert_eq!(reader.next_t::<(isize, char)>(), Some((-1, 'z')));
+        assert_eq!(reader.next_t::<i32>(), None);
+    }
+
+    #[test]
+    fn test_next_t_tuples() {
+        let mut reader = Reader::from_str("7 1 -2 3.5 a 1 2 3 4 5 6 7 8 9 10 11 12");
+        assert_eq!(reader.next_t::<(u8,)>(), Some((7,)));
+        assert_eq!(reader.next_t::<(usize, i64, f64, char)>(), Some((1, -2, 3.5, 'a')));
+        let twelve = reader.next_t::<(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize)>();
+        assert_eq!(twelve, Some((1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12)));
+    }
+
+    #[test]
+    fn test_next_vec() {
+        let mut reader = Reader::from_str("1 2 3\na bc 4");
+        assert_eq!(reader.next_vec::<u64>(3), Some(vec![1, 2, 3]));
+        assert_eq!(reader.next_vec::<String>(2), Some(vec!["a".to_string(), "bc".to_string()]));
+        assert_eq!(reader.next_vec::<i32>(0), Some(vec![]));
+        assert_eq!(reader.next_vec::<i32>(2), None);
+    }
+
+    #[test]
+    fn test_next_matrix() {
+        let mut reader = Reader::from_str("1 2\n3 4\n5 6\n1 2 3\n4 5 6\n7");
+        assert_eq!(reader.next_matrix::<u8>(3, 2), Some(vec![vec![1, 2], vec![3, 4], vec![5, 6]]));
+        assert_eq!(reader.next_matrix_flat::<u8>(2, 3), Some((vec![1, 2, 3, 4, 5, 6], 3)));
+        assert_eq!(reader.next_matrix::<u8>(1, 2), None);
+    }
+
+    #[test]
+    fn test_fill_slice() {
+        let mut reader = Reader::from_str("1 2 3 4 5");
+        let mut buffer = vec![0u64; 2];
+        assert_eq!(reader.fill_slice(&mut buffer), Some(()));
+        assert_eq!(buffer, vec![1, 2]);
+        assert_eq!(reader.fill_slice(&mut buffer[..1]), Some(()));
+        assert_eq!(buffer, vec![3, 2]);
+        assert_eq!(reader.fill_slice(&mut buffer), Some(()));
+        assert_eq!(reader.fill_slice(&mut buffer), None);
+    }
+
+    #[test]
+    fn test_next_jagged() {
+        let mut reader = Reader::from_str("3 -1 0 1\n2 5 5\n0\n3 1 2");
+        assert_eq!(reader.next_prefixed_vec::<i32>(), Some(vec![-1, 0, 1]));
+        assert_eq!(reader.next_jagged::<u8>(2), Some(vec![vec![5, 5], vec![]]));
+        assert_eq!(reader.next_jagged::<u8>(1), None);
+    }
+
+    #[test]
+    fn test_next_until_sentinel() {
+        let mut reader = Reader::from_str("5 -1 2 0 7 3 4 4\n0 0\n9 9");
+        assert_eq!(reader.next_until_sentinel::<i32>(|&x| x == 0), vec![5, -1, 2]);
+        assert_eq!(reader.next_pairs_until((0u8, 0u8)), vec![(7, 3), (4, 4)]);
+        assert_eq!(reader.next_pairs_until((0u8, 0u8)), vec![(9, 9)]);
+    }
+
+    #[test]
+    fn test_next_option() {
+        let mut reader = Reader::from_str("-1 0 3 ? 7 x");
+        assert_eq!(reader.next_option_i64(-1), Some(None));
+        assert_eq!(reader.next_option_i64(-1), Some(Some(0)));
+        assert_eq!(reader.next_option(3u8), Some(None));
+        assert_eq!(reader.next_option_token::<i32>("?"), Some(None));
+        assert_eq!(reader.next_option_token::<i32>("?"), Some(Some(7)));
+        assert_eq!(reader.next_option_token::<i32>("?"), None);
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut reader = Reader::from_str("1 2 3 4 5 6\n");
+        assert_eq!(reader.take_n::<i32>(2).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(reader.iter::<(i32, i32)>().collect::<Vec<_>>(), vec![(3, 4), (5, 6)]);
+        assert_eq!(reader.iter::<i32>().next(), None);
+    }
+
+    #[test]
+    fn test_next_columns() {
+        let mut reader = Reader::from_str("1 a\n2 b\n1 2 0.5\n3 4 1.5\n5");
+        assert_eq!(reader.next_columns2::<u8, char>(2), Some((vec![1, 2], vec!['a', 'b'])));
+        let (u, v, w) = reader.next_columns3::<usize, usize, f64>(2).unwrap();
+        assert_eq!((u, v, w), (vec![1, 3], vec![2, 4], vec![0.5, 1.5]));
+        assert_eq!(reader.next_columns2::<u8, u8>(1), None);
+    }
+
+    #[test]
+    fn test_next_array() {
+        let mut reader = Reader::from_str("1 2 3 4\n5 6 7");
+        assert_eq!(reader.next_array::<u32, 4>(), Some([1, 2, 3, 4]));
+        assert_eq!(reader.next_array::<i64, 0>(), Some([]));
+        assert_eq!(reader.next_t::<[[u8; 1]; 2]>(), Some([[5], [6]]));
+        assert_eq!(reader.next_array::<i32, 2>(), None);
+    }
+
+    #[test]
+    fn test_marker_types() {
+        let mut reader = Reader::from_str("1 5 abc #.# 0");
+        assert_eq!(reader.next_t(), Some((Usize1(0), Usize1(4))));
+        let chars: Chars = reader.next_t().unwrap();
+        assert_eq!(chars[2], 'c');
+        let bytes: Bytes = reader.next_t().unwrap();
+        assert_eq!(&bytes[..], b"#.#");
+        assert_eq!(reader.next_t::<Usize1>(), None);
+    }
+
+    #[test]
+    fn test_next_t_adjacent_chars() {
+        let mut reader = Reader::from_str("ab\ncd");
+        let chars: Vec<char> = (0..4).map(|_| reader.next_t().unwrap()).collect();
+        assert_eq!(chars, vec!['a', 'b', 'c', 'd']);
+    }
+}
@@ -0,0 +1,59 @@
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::Reader;
+
+/// A source that copies everything read from it into a file
+///
+/// Created by `Reader::recording`
+pub struct Tee<R> {
+    source: R,
+    record: File,
+}
+
+impl<R: Read> Read for Tee<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.source.read(buf)?;
+        // written straight away, so the record is complete even if the program crashes later
+        self.record.write_all(&buf[..n])?;
+        Ok(n)
+    }
+}
+
+impl Reader<Tee<io::StdinLock<'static>>> {
+    /// Instantiates a reader over stdin that also saves all input read into the file at `path`,
+    /// so a failing test that came through a pipe can be replayed afterwards
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// let mut reader = comp_io::Reader::recording("last_input.txt").unwrap();
+    /// let n = reader.next_usize().unwrap();
+    /// ```
+    pub fn recording(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Reader::from_reader(Tee {
+            source: io::stdin().lock(),
+            record: File::create(path)?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tee() {
+        let path = std::env::temp_dir().join("comp_io_test_tee.txt");
+        let tee = Tee {
+            source: io::Cursor::new("5 -2\nword\n"),
+            record: File::create(&path).unwrap(),
+        };
+        let mut reader = Reader::from_reader(tee);
+
+        assert_eq!(reader.next_pair(), Some((5, -2)));
+        assert_eq!(reader.next_token(), Some("word".to_string()));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "5 -2\nword\n");
+    }
+}
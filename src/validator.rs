@@ -0,0 +1,208 @@
+use std::io::{self, Read};
+use std::ops::RangeBounds;
+
+use crate::{ReadError, ReadErrorKind, Reader};
+
+/// Reads input under testlib-style rules, for validators written by problem setters, created by `Reader::strict`
+///
+/// Nothing is skipped: every space, line ending and the end of input has to be read explicitly, and integers
+/// can't have a `+` sign, leading zeros or be `-0`. The first violation is returned with its exact position
+///
+/// # Example:
+///
+/// ```
+/// let mut input = comp_io::Reader::from_str("2\n-5 10\n").strict();
+/// let n = input.read_int().unwrap();
+/// input.read_eoln().unwrap();
+/// for i in 0..n {
+///     input.read_int().unwrap();
+///     if i + 1 < n {
+///         input.read_space().unwrap();
+///     }
+/// }
+/// input.read_eoln().unwrap();
+/// input.read_eof().unwrap();
+/// ```
+pub struct Validator<R = io::StdinLock<'static>> {
+    reader: Reader<R>,
+}
+
+impl<R: Read> Reader<R> {
+    /// Switches to strict validation, see `Validator`
+    pub fn strict(self) -> Validator<R> {
+        Validator { reader: self }
+    }
+}
+
+impl<R: Read> Validator<R> {
+    /// Reads a non-empty run of non-whitespace bytes, leaving the byte after it unread
+    pub fn read_token(&mut self) -> Result<String, ReadError> {
+        match self.reader.peek() {
+            None => return Err(self.reader.error(ReadErrorKind::Eof)),
+            Some(b) if b.is_ascii_whitespace() => return Err(self.reader.error(ReadErrorKind::UnexpectedByte(b))),
+            Some(_) => {}
+        }
+        let token = self.reader.peek_token().unwrap_or_default();
+        let token = match std::str::from_utf8(token) {
+            Ok(token) => token.to_owned(),
+            Err(_) => return Err(self.reader.error(ReadErrorKind::Invalid)),
+        };
+        self.reader.skip_bytes(token.len());
+        Ok(token)
+    }
+
+    /// Reads an integer in canonical form, leaving the byte after it unread
+    pub fn read_int(&mut self) -> Result<i64, ReadError> {
+        self.read_int_token().map(|(value, _)| value)
+    }
+
+    /// Reads an integer in canonical form like `read_int`, checking that it is in `range`
+    pub fn read_int_in(&mut self, range: impl RangeBounds<i64>) -> Result<i64, ReadError> {
+        let (value, start) = self.read_int_token()?;
+        if range.contains(&value) {
+            Ok(value)
+        } else {
+            Err(self.reader.error_at(ReadErrorKind::OutOfRange, start))
+        }
+    }
+
+    /// Reads a single space
+    pub fn read_space(&mut self) -> Result<(), ReadError> {
+        self.read_byte(b' ')
+    }
+
+    /// Reads a single `\n`. A `\r\n` line ending is a violation
+    pub fn read_eoln(&mut self) -> Result<(), ReadError> {
+        self.read_byte(b'\n')
+    }
+
+    /// Checks that the input has ended
+    pub fn read_eof(&mut self) -> Result<(), ReadError> {
+        match self.reader.peek() {
+            None => Ok(()),
+            Some(b) => Err(self.reader.error(ReadErrorKind::UnexpectedByte(b))),
+        }
+    }
+
+    /// Returns the underlying reader
+    pub fn into_inner(self) -> Reader<R> {
+        self.reader
+    }
+
+    /// Reads an integer in canonical form, returning it with the input offset it started at
+    ///
+    /// Only the offset is kept, since working out a line and column scans the buffer
+    fn read_int_token(&mut self) -> Result<(i64, usize), ReadError> {
+        // taken before the token is read, since reading it can refill the buffer or take pushed back bytes
+        let start = self.reader.offset();
+        let token = self.read_token()?;
+        match parse_canonical(token.as_bytes()) {
+            Ok(value) => Ok((value, start)),
+            Err(kind) => Err(self.reader.error_at(kind, start)),
+        }
+    }
+
+    fn read_byte(&mut self, expected: u8) -> Result<(), ReadError> {
+        match self.reader.peek() {
+            Some(b) if b == expected => {
+                self.reader.next();
+                Ok(())
+            }
+            Some(b) => Err(self.reader.error(ReadErrorKind::UnexpectedByte(b))),
+            None => Err(self.reader.error(ReadErrorKind::Eof)),
+        }
+    }
+}
+
+/// Parses an integer with an optional `-`, no leading zeros and no `-0`
+fn parse_canonical(token: &[u8]) -> Result<i64, ReadErrorKind> {
+    let (neg, digits) = match token.strip_prefix(b"-") {
+        Some(digits) => (true, digits),
+        None => (false, token),
+    };
+    if let Some(&b) = digits.iter().find(|b| !b.is_ascii_digit()) {
+        return Err(ReadErrorKind::UnexpectedByte(b));
+    }
+    if digits.is_empty() || (digits[0] == b'0' && (digits.len() > 1 || neg)) {
+        return Err(ReadErrorKind::Invalid);
+    }
+    // accumulate as a negative number so `i64::MIN` fits
+    let mut r: i64 = 0;
+    for &d in digits {
+        r = r
+            .checked_mul(10)
+            .and_then(|r| r.checked_sub((d - b'0') as i64))
+            .ok_or(ReadErrorKind::Overflow)?;
+    }
+    if neg {
+        Ok(r)
+    } else {
+        r.checked_neg().ok_or(ReadErrorKind::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Position;
+
+    fn first_error(input: &str) -> ReadError {
+        let mut input = Reader::from_str(input).strict();
+        input
+            .read_int()
+            .and_then(|_| input.read_space())
+            .and_then(|_| input.read_token())
+            .and_then(|_| input.read_eoln())
+            .and_then(|_| input.read_eof())
+            .unwrap_err()
+    }
+
+    #[test]
+    fn test_validator() {
+        let mut input = Reader::from_str("-9223372036854775808 ab\n").strict();
+        assert_eq!(input.read_int(), Ok(i64::MIN));
+        input.read_space().unwrap();
+        assert_eq!(input.read_token().unwrap(), "ab");
+        input.read_eoln().unwrap();
+        input.read_eof().unwrap();
+
+        let error = first_error("1  ab\n");
+        assert_eq!(error.kind, ReadErrorKind::UnexpectedByte(b' '));
+        assert_eq!(error.position, Position { line: 1, column: 3 });
+        assert_eq!(first_error("1 ab \n").kind, ReadErrorKind::UnexpectedByte(b' '));
+        assert_eq!(first_error("1 ab\r\n").kind, ReadErrorKind::UnexpectedByte(b'\r'));
+        assert_eq!(first_error("1 ab").kind, ReadErrorKind::Eof);
+        assert_eq!(first_error("1 ab\n\n").kind, ReadErrorKind::UnexpectedByte(b'\n'));
+        assert_eq!(first_error("01 ab\n").kind, ReadErrorKind::Invalid);
+        assert_eq!(first_error("01 ab\n").position, Position { line: 1, column: 1 });
+        assert_eq!(first_error("-0 ab\n").kind, ReadErrorKind::Invalid);
+        assert_eq!(first_error("+1 ab\n").kind, ReadErrorKind::UnexpectedByte(b'+'));
+        assert_eq!(first_error("9223372036854775808 ab\n").kind, ReadErrorKind::Overflow);
+
+        let mut input = Reader::from_str("7 8").strict();
+        assert_eq!(input.read_int_in(1..=7), Ok(7));
+        input.read_space().unwrap();
+        let error = input.read_int_in(1..=7).unwrap_err();
+        assert_eq!(error.kind, ReadErrorKind::OutOfRange);
+        assert_eq!(error.position, Position { line: 1, column: 3 });
+
+        // refills drop the start of the buffer, which mustn't move the reported column
+        let input = io::Cursor::new("1 22\n333 4444");
+        let mut input = Reader::builder().capacity(3).source(input).strict();
+        assert_eq!(input.read_int_in(0..10), Ok(1));
+        input.read_space().unwrap();
+        assert_eq!(input.read_int_in(0..10).unwrap_err().position, Position { line: 1, column: 3 });
+        input.read_eoln().unwrap();
+        assert_eq!(input.read_int_in(0..10).unwrap_err().position, Position { line: 2, column: 1 });
+        input.read_space().unwrap();
+        assert_eq!(input.read_int().unwrap(), 4444);
+        assert_eq!(input.read_byte(b'5').unwrap_err().position, Position { line: 2, column: 9 });
+
+        // a pushed back token is reported where it was pushed back
+        let mut reader = Reader::from_str("x50");
+        reader.next_char();
+        reader.unread(b'7');
+        let error = reader.strict().read_int_in(0..10).unwrap_err();
+        assert_eq!(error.position, Position { line: 1, column: 2 });
+    }
+}
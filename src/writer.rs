@@ -0,0 +1,758 @@
+use std::env;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
+use std::path::Path;
+
+/// Bytes collected before the buffer is written out
+const CAPACITY: usize = 1 << 20;
+
+/// Writes output to stdout through a large buffer, which is much faster than `println!` for many lines
+///
+/// The sink defaults to stdout, but can be any type implementing `Write`, such as a file or a `Vec<u8>` in tests.
+/// Output is only written once the buffer fills up, `flush` is called, or the writer is dropped
+///
+/// # Example:
+///
+/// ```
+/// let mut writer = comp_io::Writer::new();
+/// writer.write_str("answer: ");
+/// writer.write_int(-42);
+/// writer.newline();
+/// writer.flush();
+/// ```
+pub struct Writer<W: Write = io::StdoutLock<'static>> {
+    /// Only taken out by `into_inner`
+    out: Option<W>,
+    buffer: Vec<u8>,
+    /// Flush as soon as a line is complete, for interactive problems
+    autoflush: bool,
+    /// Number of open `Scope`s, while which nothing is flushed so their output can still be discarded
+    scopes: usize,
+}
+
+impl Default for Writer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Writer {
+    /// Instantiates a writer over stdout, which stays locked for the writer's lifetime
+    pub fn new() -> Self {
+        Writer::from_writer(io::stdout().lock())
+    }
+}
+
+impl Writer<File> {
+    /// Instantiates a writer that creates or truncates the file at `path`
+    pub fn to_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Writer::from_writer(File::create(path)?))
+    }
+}
+
+impl Writer<Box<dyn Write>> {
+    /// Instantiates a writer over a local answer file if one is requested, and stdout otherwise
+    ///
+    /// The file is the path in the `COMP_IO_OUTPUT` environment variable, created or truncated
+    /// like `Writer::to_file`. Together with `Reader::auto`, this lets a stress script compare
+    /// answer files without redirecting the program's output
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `COMP_IO_OUTPUT` is set but the file can't be created
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// let mut writer = comp_io::Writer::auto();
+    /// writer.write_int(42);
+    /// writer.newline();
+    /// ```
+    pub fn auto() -> Self {
+        let out: Box<dyn Write> = match env::var_os("COMP_IO_OUTPUT") {
+            Some(path) => match File::create(&path) {
+                Ok(file) => Box::new(file),
+                Err(e) => panic!("couldn't create COMP_IO_OUTPUT file {path:?}: {e}"),
+            },
+            None => Box::new(io::stdout().lock()),
+        };
+        Writer::from_writer(out)
+    }
+}
+
+impl Writer<Vec<u8>> {
+    /// Instantiates a writer that keeps its output in memory, to be checked with `into_string`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut writer = comp_io::Writer::in_memory();
+    /// writer.writeln_joined([1, 2, 3], " ");
+    /// assert_eq!(writer.into_string(), "1 2 3\n");
+    /// ```
+    pub fn in_memory() -> Self {
+        Writer::from_writer(Vec::new())
+    }
+
+    /// Returns everything written as a `String`
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the output is not valid UTF-8
+    pub fn into_string(self) -> String {
+        String::from_utf8(self.into_inner()).expect("output is not valid UTF-8")
+    }
+}
+
+impl<W: Write> Writer<W> {
+    /// Instantiates a writer over any sink implementing `Write`
+    pub fn from_writer(out: W) -> Self {
+        Writer {
+            out: Some(out),
+            buffer: Vec::with_capacity(CAPACITY),
+            autoflush: false,
+            scopes: 0,
+        }
+    }
+
+    /// Flushes the buffer and returns the sink
+    pub fn into_inner(mut self) -> W {
+        self.flush();
+        self.out.take().expect("sink is only taken once")
+    }
+
+    /// Makes the writer flush whenever a line is complete, since interactive problems need every query flushed
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut writer = comp_io::Writer::new().interactive();
+    /// comp_io::outln!(writer, "? {}", 5); // written straight away
+    /// ```
+    pub fn interactive(mut self) -> Self {
+        self.autoflush = true;
+        self
+    }
+
+    /// Writes an integer of any primitive type
+    pub fn write_int<T: Integer>(&mut self, n: T) {
+        n.write_to(&mut self.buffer);
+        self.flush_if_full();
+    }
+
+    /// Writes a string as-is
+    pub fn write_str(&mut self, s: &str) {
+        self.buffer.extend_from_slice(s.as_bytes());
+        self.flush_if_full();
+    }
+
+    /// Writes `x` rounded to `digits` decimal places, the same as `format!("{x:.digits$}")` or `printf("%.*f")`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut writer = comp_io::Writer::new();
+    /// writer.write_f64(std::f64::consts::PI, 9); // 3.141592654
+    /// ```
+    pub fn write_f64(&mut self, x: f64, digits: usize) {
+        let p = x.abs() * 10f64.powi(digits.min(15) as i32);
+        // The product is off by at most half an ulp, well under 1e-3 below 2^40, so unless it is that close to a
+        // tie it rounds the same way as the exact value. Everything else goes through the exact std formatting
+        if x.is_finite() && digits <= 15 && p < (1u64 << 40) as f64 && (p.fract() - 0.5).abs() > 1e-3 {
+            let r = p.round() as u64;
+            let scale = 10u64.pow(digits as u32);
+            if x.is_sign_negative() {
+                self.buffer.push(b'-');
+            }
+            (r / scale).write_to(&mut self.buffer);
+            if digits > 0 {
+                self.buffer.push(b'.');
+                let frac = r % scale;
+                let start = self.buffer.len();
+                frac.write_to(&mut self.buffer);
+                let zeros = digits - (self.buffer.len() - start);
+                self.buffer.splice(start..start, std::iter::repeat_n(b'0', zeros));
+            }
+        } else {
+            let _ = write!(self.buffer, "{x:.digits$}");
+        }
+        self.flush_if_full();
+    }
+
+    /// Writes `x` with the fewest digits that still read back as exactly `x`, like `format!("{x}")`
+    pub fn write_f64_shortest(&mut self, x: f64) {
+        let _ = write!(self.buffer, "{x}");
+        self.flush_if_full();
+    }
+
+    /// Writes the items of `items` separated by `sep`, without building a `String` first
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let answer = vec![3, 1, 2];
+    /// let mut writer = comp_io::Writer::new();
+    /// writer.write_joined(&answer, " "); // 3 1 2
+    /// ```
+    pub fn write_joined<I>(&mut self, items: I, sep: &str)
+    where
+        I: IntoIterator,
+        I::Item: Writable,
+    {
+        let mut items = items.into_iter();
+        if let Some(first) = items.next() {
+            first.append_to(&mut self.buffer);
+            for item in items {
+                self.buffer.extend_from_slice(sep.as_bytes());
+                item.append_to(&mut self.buffer);
+                self.flush_if_full();
+            }
+        }
+        self.flush_if_full();
+    }
+
+    /// Same as `write_joined`, followed by a `\n`
+    pub fn writeln_joined<I>(&mut self, items: I, sep: &str)
+    where
+        I: IntoIterator,
+        I::Item: Writable,
+    {
+        self.write_joined(items, sep);
+        self.newline();
+    }
+
+    /// Writes each item of `items` on its own line
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let answers = [4, 8, 15];
+    /// let mut writer = comp_io::Writer::new();
+    /// writer.writeln_each(answers); // 4\n8\n15\n
+    /// ```
+    pub fn writeln_each<I>(&mut self, items: I)
+    where
+        I: IntoIterator,
+        I::Item: Writable,
+    {
+        for item in items {
+            item.append_to(&mut self.buffer);
+            self.newline();
+        }
+    }
+
+    /// Writes each row of `grid` on its own line, with values separated by spaces.
+    /// For a grid of chars printed without spaces, use `writeln_joined(row, "")` per row
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let grid = vec![vec![1, 2], vec![3, 4]];
+    /// let mut writer = comp_io::Writer::new();
+    /// writer.write_grid(&grid); // 1 2\n3 4\n
+    /// ```
+    pub fn write_grid<T: Writable>(&mut self, grid: &[Vec<T>]) {
+        for row in grid {
+            self.writeln_joined(row, " ");
+        }
+    }
+
+    /// Writes `value` right-aligned in a field of `width` bytes, padded with `fill` on the left.
+    /// With `'0'` as the fill, a minus sign stays in front of the zeros like `format!("{:05}")`
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut writer = comp_io::Writer::in_memory();
+    /// writer.write_padded(42, 5, '0');
+    /// writer.write_padded(-7, 4, '0');
+    /// writer.write_padded("ab", 4, ' ');
+    /// assert_eq!(writer.into_string(), "00042-007  ab");
+    /// ```
+    pub fn write_padded<T: Writable>(&mut self, value: T, width: usize, fill: char) {
+        let start = self.buffer.len();
+        value.append_to(&mut self.buffer);
+        let len = self.buffer.len() - start;
+        if len < width {
+            let at = if fill == '0' && self.buffer.get(start) == Some(&b'-') { start + 1 } else { start };
+            let mut encoded = [0; 4];
+            let fill = fill.encode_utf8(&mut encoded).as_bytes();
+            let padding = fill.iter().copied().cycle().take(fill.len() * (width - len));
+            self.buffer.splice(at..at, padding);
+        }
+        self.flush_if_full();
+    }
+
+    /// Writes `value` left-aligned in a field of `width` bytes, padded with `fill` on the right
+    pub fn write_padded_left<T: Writable>(&mut self, value: T, width: usize, fill: char) {
+        let start = self.buffer.len();
+        value.append_to(&mut self.buffer);
+        for _ in self.buffer.len() - start..width {
+            self.buffer.extend_from_slice(fill.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        self.flush_if_full();
+    }
+
+    /// Writes the value in `value`, or `sentinel` if there is none
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut writer = comp_io::Writer::in_memory();
+    /// writer.write_opt(Some(5), -1);
+    /// writer.newline();
+    /// writer.write_opt(None::<u64>, "IMPOSSIBLE");
+    /// assert_eq!(writer.into_string(), "5\nIMPOSSIBLE");
+    /// ```
+    pub fn write_opt<T: Writable, S: Writable>(&mut self, value: Option<T>, sentinel: S) {
+        match value {
+            Some(value) => value.append_to(&mut self.buffer),
+            None => sentinel.append_to(&mut self.buffer),
+        }
+        self.flush_if_full();
+    }
+
+    /// Writes the `Case #i: ` prefix used by Code Jam style problems, see also the `case!` macro
+    pub fn case(&mut self, i: usize) {
+        self.write_str("Case #");
+        self.write_int(i);
+        self.write_str(": ");
+    }
+
+    /// Writes a `Case #i:` line, for answers that start on the next line
+    pub fn case_multiline(&mut self, i: usize) {
+        self.write_str("Case #");
+        self.write_int(i);
+        self.write_str(":");
+        self.newline();
+    }
+
+    /// Writes formatted output, so `write!` and the `out!` macros can target the writer
+    pub fn write_fmt(&mut self, args: fmt::Arguments<'_>) {
+        let _ = self.buffer.write_fmt(args);
+        self.flush_if_full();
+    }
+
+    /// Writes any `Writable` value, such as a number, string or char
+    pub fn write<T: Writable>(&mut self, value: T) {
+        value.append_to(&mut self.buffer);
+        self.flush_if_full();
+    }
+
+    /// Writes `bits` as a string of `0`s and `1`s
+    pub fn write_bits(&mut self, bits: &[bool]) {
+        self.buffer.extend(bits.iter().map(|&bit| b'0' + bit as u8));
+        self.flush_if_full();
+    }
+
+    /// Writes the first `len` bits of a packed bitset as a string of `0`s and `1`s, bit 0 first,
+    /// in the same layout `Reader::next_bitset` reads
+    ///
+    /// # Panics:
+    ///
+    /// Panics if `words` holds fewer than `len` bits
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut writer = comp_io::Writer::in_memory();
+    /// writer.write_bitset(&[0b1101], 5);
+    /// writer.write_str(" ");
+    /// writer.write_bits(&[true, false]);
+    /// assert_eq!(writer.into_string(), "10110 10");
+    /// ```
+    pub fn write_bitset(&mut self, words: &[u64], len: usize) {
+        assert!(len <= words.len() * 64, "bitset of {} words has fewer than {len} bits", words.len());
+        self.buffer.extend((0..len).map(|i| b'0' + (words[i / 64] >> (i % 64) & 1) as u8));
+        self.flush_if_full();
+    }
+
+    /// Writes raw bytes as-is, without going through `str`
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+        self.flush_if_full();
+    }
+
+    /// Appends an answer that was already built up as bytes. When nothing else is buffered and the blob's
+    /// allocation is at least as large as the buffer, it becomes the buffer, so it isn't copied
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut answer = Vec::new();
+    /// answer.extend_from_slice(b"1 2 3\n");
+    /// let mut writer = comp_io::Writer::in_memory();
+    /// writer.append(answer);
+    /// writer.write_bytes(b"done\n");
+    /// assert_eq!(writer.into_string(), "1 2 3\ndone\n");
+    /// ```
+    pub fn append(&mut self, blob: Vec<u8>) {
+        // a smaller allocation is copied instead, so the buffer keeps its capacity for later writes
+        if self.buffer.is_empty() && blob.capacity() >= self.buffer.capacity() {
+            self.buffer = blob;
+        } else {
+            self.buffer.extend_from_slice(&blob);
+        }
+        self.flush_if_full();
+    }
+
+    /// Writes a `\n`
+    pub fn newline(&mut self) {
+        self.buffer.push(b'\n');
+        self.flush_if_full();
+    }
+
+    /// Writes everything buffered so far to the sink
+    ///
+    /// # Panics:
+    ///
+    /// Panics if the sink can't be written to
+    pub fn flush(&mut self) {
+        if let Some(out) = &mut self.out {
+            out.write_all(&self.buffer).expect("failed to write output");
+            out.flush().expect("failed to write output");
+        }
+        self.buffer.clear();
+    }
+
+    /// Starts collecting output that can be thrown away with `Scope::discard`, such as a test case that turns
+    /// out to be impossible halfway through. The output is kept when the scope is dropped
+    ///
+    /// # Example:
+    ///
+    /// ```
+    /// let mut writer = comp_io::Writer::in_memory();
+    /// for case in [vec![1, 2], vec![3, -1]] {
+    ///     let mut scope = writer.scope();
+    ///     for x in case {
+    ///         if x < 0 {
+    ///             scope.discard();
+    ///             writer.write_str("IMPOSSIBLE\n");
+    ///             break;
+    ///         }
+    ///         scope.write_int(x);
+    ///         scope.newline();
+    ///     }
+    /// }
+    /// assert_eq!(writer.into_string(), "1\n2\nIMPOSSIBLE\n");
+    /// ```
+    pub fn scope(&mut self) -> Scope<'_, W> {
+        self.scopes += 1;
+        Scope {
+            start: self.buffer.len(),
+            writer: self,
+        }
+    }
+
+    fn flush_if_full(&mut self) {
+        if self.scopes > 0 {
+            return;
+        }
+        // with autoflush the buffer never holds more than a line, so the scan is short
+        if self.buffer.len() >= CAPACITY || (self.autoflush && self.buffer.contains(&b'\n')) {
+            self.flush();
+        }
+    }
+}
+
+/// Output that is only kept if the scope isn't discarded, created by `Writer::scope`
+///
+/// Derefs to the `Writer`, so all of its methods can be used while the scope is open
+pub struct Scope<'a, W: Write> {
+    writer: &'a mut Writer<W>,
+    start: usize,
+}
+
+impl<W: Write> Scope<'_, W> {
+    /// Throws away everything written since the scope was opened
+    pub fn discard(self) {
+        self.writer.buffer.truncate(self.start);
+    }
+}
+
+impl<W: Write> Deref for Scope<'_, W> {
+    type Target = Writer<W>;
+
+    fn deref(&self) -> &Writer<W> {
+        self.writer
+    }
+}
+
+impl<W: Write> DerefMut for Scope<'_, W> {
+    fn deref_mut(&mut self) -> &mut Writer<W> {
+        self.writer
+    }
+}
+
+impl<W: Write> Drop for Scope<'_, W> {
+    fn drop(&mut self) {
+        self.writer.scopes -= 1;
+        self.writer.flush_if_full();
+    }
+}
+
+/// Writes out whatever is still buffered, so returning early from `main` doesn't lose the end of the output
+impl<W: Write> Drop for Writer<W> {
+    fn drop(&mut self) {
+        if let Some(out) = &mut self.out {
+            // errors can't be reported from here, and panicking in drop could abort
+            let _ = out.write_all(&self.buffer);
+            let _ = out.flush();
+        }
+    }
+}
+
+/// Primitive integers that `Writer::write_int` can format
+pub trait Integer: Copy {
+    /// Appends the decimal digits of `self` to `out`
+    fn write_to(self, out: &mut Vec<u8>);
+}
+
+/// Values that `Writer::write_joined` can write: integers, floats in their shortest form, chars and strings
+pub trait Writable {
+    /// Appends `self` to `out`
+    fn append_to(&self, out: &mut Vec<u8>);
+}
+
+macro_rules! impl_writable_integer {
+    ($($t:ty)*) => {
+        $(
+            impl Writable for $t {
+                fn append_to(&self, out: &mut Vec<u8>) {
+                    self.write_to(out);
+                }
+            }
+        )*
+    };
+}
+
+impl_writable_integer!(u8 i8 u16 i16 u32 i32 u64 i64 u128 i128 usize isize);
+
+impl<T: Writable + ?Sized> Writable for &T {
+    fn append_to(&self, out: &mut Vec<u8>) {
+        (**self).append_to(out);
+    }
+}
+
+impl Writable for str {
+    fn append_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Writable for String {
+    fn append_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Writable for char {
+    fn append_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.encode_utf8(&mut [0; 4]).as_bytes());
+    }
+}
+
+impl Writable for f64 {
+    fn append_to(&self, out: &mut Vec<u8>) {
+        let _ = write!(out, "{self}");
+    }
+}
+
+impl Writable for f32 {
+    fn append_to(&self, out: &mut Vec<u8>) {
+        let _ = write!(out, "{self}");
+    }
+}
+
+/// Formats into a stack buffer from the last digit backwards, avoiding `fmt` machinery
+macro_rules! impl_integer {
+    ($unsigned:ty, $($signed:ty)?) => {
+        impl Integer for $unsigned {
+            fn write_to(self, out: &mut Vec<u8>) {
+                let mut digits = [0u8; 40];
+                let mut i = digits.len();
+                let mut n = self;
+                loop {
+                    i -= 1;
+                    digits[i] = b'0' + (n % 10) as u8;
+                    n /= 10;
+                    if n == 0 {
+                        break;
+                    }
+                }
+                out.extend_from_slice(&digits[i..]);
+            }
+        }
+        $(
+            impl Integer for $signed {
+                fn write_to(self, out: &mut Vec<u8>) {
+                    if self < 0 {
+                        out.push(b'-');
+                    }
+                    self.unsigned_abs().write_to(out);
+                }
+            }
+        )?
+    };
+}
+
+impl_integer!(u8, i8);
+impl_integer!(u16, i16);
+impl_integer!(u32, i32);
+impl_integer!(u64, i64);
+impl_integer!(u128, i128);
+impl_integer!(usize, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_f64() {
+        let mut writer = Writer::in_memory();
+        let mut state: u64 = 12345;
+        let mut values = vec![0.0, -0.0, 0.125, 2.5, -0.001, 1.005, 1e300, f64::NAN, f64::NEG_INFINITY, 0.5e-7];
+        for _ in 0..10_000 {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let scale = 10f64.powi((state >> 60) as i32 - 6);
+            values.push(((state >> 11) as f64 / (1u64 << 53) as f64 - 0.5) * scale);
+        }
+        for &x in &values {
+            for digits in [0, 1, 2, 3, 6, 9, 15, 17] {
+                writer.buffer.clear();
+                writer.write_f64(x, digits);
+                assert_eq!(String::from_utf8_lossy(&writer.buffer), format!("{x:.digits$}"));
+            }
+        }
+    }
+
+    #[test]
+    fn test_write_joined() {
+        let mut writer = Writer::in_memory();
+        let values = vec![1, -2, 3];
+        writer.writeln_joined(&values, " ");
+        writer.writeln_joined(["a", "b"], ", ");
+        writer.write_joined(Vec::<u8>::new(), " ");
+        writer.writeln_joined("xyz".chars(), "-");
+        writer.writeln_joined([0.5, 2.0], " ");
+        assert_eq!(writer.into_string(), "1 -2 3\na, b\nx-y-z\n0.5 2\n");
+    }
+
+    #[test]
+    fn test_write_lines() {
+        let mut writer = Writer::in_memory();
+        writer.writeln_each(["yes", "no"]);
+        writer.write_grid(&[vec!['a', 'b'], vec![], vec!['c']]);
+        writer.write_grid::<u8>(&[]);
+        assert_eq!(writer.into_string(), "yes\nno\na b\n\nc\n");
+    }
+
+    #[test]
+    fn test_out() {
+        let mut writer = Writer::in_memory();
+        crate::out!(writer, "{}-{:>3}", 1, "x");
+        crate::outln!(writer, "!");
+        crate::outln!(writer);
+        crate::outln!(&mut writer, "{:.1}", 2.25);
+        assert_eq!(writer.into_string(), "1-  x!\n\n2.2\n");
+    }
+
+    #[test]
+    fn test_append() {
+        let mut writer = Writer::in_memory();
+        writer.append(b"1 2\n".to_vec());
+        assert!(writer.buffer.capacity() >= CAPACITY);
+        writer.append(b"3\n".to_vec());
+        let blob = Vec::with_capacity(2 * CAPACITY);
+        let ptr = blob.as_ptr();
+        let mut large = Writer::in_memory();
+        large.append(blob);
+        assert_eq!(large.buffer.as_ptr(), ptr);
+        assert_eq!(writer.into_string(), "1 2\n3\n");
+    }
+
+    #[test]
+    fn test_interactive() {
+        let mut writer = Writer::in_memory().interactive();
+        writer.write_str("? 1");
+        assert_eq!(writer.out.as_deref(), Some(&b""[..]));
+        writer.newline();
+        writer.write_str("? 2\n? ");
+        assert_eq!(writer.out.as_deref(), Some(&b"? 1\n? 2\n? "[..]));
+    }
+
+    #[test]
+    fn test_to_file() {
+        let path = std::env::temp_dir().join("comp_io_test_writer.txt");
+        let mut writer = Writer::to_file(&path).unwrap();
+        writer.write_int(5u8);
+        drop(writer);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "5");
+    }
+
+    #[test]
+    fn test_case() {
+        let mut writer = Writer::in_memory();
+        crate::case!(writer, 1, "{}", 42);
+        writer.case(2);
+        writer.write_str("IMPOSSIBLE");
+        writer.newline();
+        writer.case_multiline(3);
+        writer.write_grid(&[vec![1, 2]]);
+        assert_eq!(writer.into_string(), "Case #1: 42\nCase #2: IMPOSSIBLE\nCase #3:\n1 2\n");
+    }
+
+    #[test]
+    fn test_write_padded() {
+        for (n, width) in [(5, 3), (-12, 6), (123456, 3), (0, 1), (i64::MIN, 22)] {
+            let mut writer = Writer::in_memory();
+            writer.write_padded(n, width, '0');
+            writer.write_str("|");
+            writer.write_padded(n, width, ' ');
+            writer.write_str("|");
+            writer.write_padded_left(n, width, ' ');
+            assert_eq!(writer.into_string(), format!("{n:0width$}|{n:>width$}|{n:<width$}"));
+        }
+    }
+
+    #[test]
+    fn test_scope() {
+        let mut writer = Writer::in_memory().interactive();
+        writer.write_str("a\n");
+        {
+            let mut scope = writer.scope();
+            scope.write_str("b\n");
+            let mut inner = scope.scope();
+            inner.write_str("c\n");
+            inner.discard();
+            assert_eq!(scope.out.as_deref(), Some(&b"a\n"[..]));
+        }
+        assert_eq!(writer.into_string(), "a\nb\n");
+    }
+
+    #[test]
+    fn test_write_bitset_round_trip() {
+        let bits: String = (0..150).map(|i| if i % 3 == 0 || i == 149 { '1' } else { '0' }).collect();
+        let words = crate::Reader::from_str(&bits).next_bitset().unwrap();
+        let mut writer = Writer::in_memory();
+        writer.write_bitset(&words, bits.len());
+        assert_eq!(writer.into_string(), bits);
+    }
+
+    #[test]
+    fn test_write_int() {
+        let mut out = Vec::new();
+        for n in [0, 7, -7, 1_000_000, i64::MIN, i64::MAX] {
+            n.write_to(&mut out);
+            out.push(b' ');
+        }
+        u128::MAX.write_to(&mut out);
+        i8::MIN.write_to(&mut out);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "0 7 -7 1000000 -9223372036854775808 9223372036854775807 340282366920938463463374607431768211455-128"
+        );
+    }
+}
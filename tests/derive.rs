@@ -0,0 +1,76 @@
+use comp_io::Reader;
+use comp_io_derive::{Readable, ReadableEnum};
+
+#[derive(Readable, Debug, PartialEq)]
+struct Edge {
+    u: usize,
+    pub v: usize,
+    #[allow(dead_code)]
+    w: i64,
+}
+
+#[derive(Readable, Debug, PartialEq)]
+struct Query(char, Vec<u32>);
+
+#[derive(Readable, Debug, PartialEq)]
+struct Marker;
+
+#[derive(Debug, PartialEq)]
+struct Pair<A, B>(A, B);
+
+impl<A: comp_io::Readable, B: comp_io::Readable> comp_io::Readable for Pair<A, B> {
+    fn read<R: std::io::Read>(reader: &mut Reader<R>) -> Option<Self> {
+        Some(Pair(reader.next_t()?, reader.next_t()?))
+    }
+}
+
+#[derive(Readable, Debug, PartialEq)]
+struct Nested {
+    edge: Edge,
+    pair: Pair<u8, u16>,
+    grid: [[i32; 2]; 2],
+}
+
+#[test]
+fn test_derive_named() {
+    let mut reader = Reader::from_str("1 2 -5\n3 4 7");
+    assert_eq!(reader.next_t(), Some(Edge { u: 1, v: 2, w: -5 }));
+    let edges: Vec<Edge> = reader.iter().collect();
+    assert_eq!(edges, vec![Edge { u: 3, v: 4, w: 7 }]);
+}
+
+#[test]
+fn test_derive_tuple_and_unit() {
+    let mut reader = Reader::from_str("q 2 10 20");
+    assert_eq!(reader.next_t(), Some(Query('q', vec![10, 20])));
+    assert_eq!(reader.next_t(), Some(Marker));
+}
+
+#[test]
+fn test_derive_generic_field_types() {
+    let mut reader = Reader::from_str("1 2 3 4 5 6 7 8 9");
+    let nested = Nested {
+        edge: Edge { u: 1, v: 2, w: 3 },
+        pair: Pair(4, 5),
+        grid: [[6, 7], [8, 9]],
+    };
+    assert_eq!(reader.next_t(), Some(nested));
+}
+
+#[derive(ReadableEnum, Debug, PartialEq)]
+enum Command {
+    Add,
+    Remove = 5,
+    #[token = "?"]
+    Ask,
+}
+
+#[test]
+fn test_derive_enum() {
+    let mut reader = Reader::from_str("ADD remove ? ask\nAdd 3");
+    assert_eq!(reader.next_t(), Some(Command::Add));
+    assert_eq!(reader.next_enum(), Some(Command::Remove));
+    assert_eq!(reader.next_t(), Some(Command::Ask));
+    assert_eq!(reader.next_t::<Command>(), None);
+    assert_eq!(reader.next_t(), Some((Command::Add, 3u8)));
+}